@@ -0,0 +1,91 @@
+// src/bitset.rs
+
+//! The `bitset` module provides a compact set of flat cell indices.
+//!
+//! Many board computations only need to know *whether* a cell is a mine. A
+//! `Vec<bool>` spends a whole byte on that answer; a bitset packs 64 answers
+//! into a single `u64`. For the huge boards that N dimensions make easy to
+//! create, that is the difference between a lookup table that fits in the CPU
+//! cache and one that doesn't.
+
+/// A fixed-size set of bits, addressed by flat cell index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bitset {
+    /// The bits, 64 per word. Bit `i` lives in word `i / 64` at position `i % 64`.
+    words: Vec<u64>,
+
+    /// The number of addressable bits. The last word may have unused bits,
+    /// which are always kept at zero so that `count_ones` stays correct.
+    len: usize,
+}
+
+impl Bitset {
+    /// Creates a bitset of `len` bits, all cleared.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    /// Returns the number of addressable bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bitset has no addressable bits at all.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, just like indexing a `Vec` would.
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "bit index {index} out of bounds");
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Sets the bit at `index` to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "bit index {index} out of bounds");
+        let mask = 1 << (index % 64);
+        if value {
+            self.words[index / 64] |= mask;
+        } else {
+            self.words[index / 64] &= !mask;
+        }
+    }
+
+    /// Returns how many bits are set.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_get_across_word_boundary() {
+        let mut bits = Bitset::new(130);
+        bits.set(0, true);
+        bits.set(63, true);
+        bits.set(64, true);
+        bits.set(129, true);
+        assert!(bits.get(63) && bits.get(64) && bits.get(129));
+        assert!(!bits.get(1) && !bits.get(128));
+        assert_eq!(bits.count_ones(), 4);
+
+        bits.set(63, false);
+        assert!(!bits.get(63));
+        assert_eq!(bits.count_ones(), 3);
+    }
+}
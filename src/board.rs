@@ -8,22 +8,219 @@
 //! - Calculating the number of adjacent mines for each cell.
 //! - Handling the logic for revealing cells.
 
-use crate::cell::{Cell, CellKind, CellState};
-use crate::coordinates::{get_neighbors, to_coords, to_index};
+use crate::bitset::Bitset;
+use crate::cell::{Cell, CellKind, CellState, MarkCycle};
+use crate::coordinates::{
+    Coordinates, apply_offset_wrapping, default_axis_label, format_labeled, get_neighbor_indices,
+    get_neighbor_indices_by_order, get_neighbors_by_order, get_neighbors_radius,
+    get_neighbors_wrapping, range_iter, to_coords, to_index,
+};
+use crate::error::BoardError;
+use crate::solver::guesses_needed;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+use std::collections::VecDeque;
+
+/// How many random layouts a filtering generator (such as
+/// `Board::new_with_min_3bv`) tries before giving up. Without a cap, an
+/// impossible requirement would make generation loop forever.
+pub const MAX_GENERATION_ATTEMPTS: usize = 10_000;
+
+/// The largest board, in cells, that `Board::new` agrees to create.
+///
+/// N-dimensional boards grow *fast*: `vec![1000; 8]` has 10^24 cells, which
+/// doesn't even fit in a `usize`. This cap (about 268 million cells, or
+/// roughly 800 MB of cells) turns an accidental giant board into a clear
+/// error instead of an out-of-memory crash. Use `Board::new_with_max_cells`
+/// to choose a different limit.
+pub const DEFAULT_MAX_CELLS: usize = 1 << 28;
+
+/// The value `Board::adjacency_grid` reports for mine cells.
+///
+/// Mines have no adjacent-mine count of their own. Counts are stored as
+/// `u16` (a 6D cell already has 728 neighbors, more than a `u8` can count)
+/// and saturate one below this value, so it can never be a real count and
+/// the grid stays a plain `Vec<u16>`.
+pub const MINE_SENTINEL: u16 = u16::MAX;
+
+/// What happened when one or more cells were revealed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevealOutcome {
+    /// Only safe cells were revealed.
+    Safe {
+        /// Every cell that went from hidden to revealed, in reveal order.
+        revealed: Vec<Coordinates>,
+        /// The number on the clicked cell, when a single cell was clicked
+        /// and it turned out to be a number (so nothing cascaded). This
+        /// saves front-ends a lookup to show it. `None` for cascades and
+        /// for reveals of several cells at once.
+        adjacent_mines: Option<u16>,
+    },
+    /// A mine was revealed at these coordinates.
+    HitMine(Coordinates),
+    /// Nothing changed, because every targeted cell was already revealed
+    /// (or flagged). Front-ends can use this to tell a redundant click from
+    /// a real one, e.g. to chord when an open number is clicked again.
+    NoOp,
+}
+
+/// How a generator chooses where the mines go.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Placement {
+    /// Every cell is equally likely to get a mine. This is the classic
+    /// behavior, and it happily produces clumps of touching mines.
+    #[default]
+    Uniform,
+    /// Mines avoid touching each other where possible, which spreads them
+    /// more evenly over the board. Clumps only appear once the board is too
+    /// dense to keep every mine apart.
+    Spread,
+}
+
+/// Which cells count as a cell's neighbors, both for the numbers and for
+/// everything built on them (flood fills, chords, hints).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Adjacency {
+    /// Every cell that touches this one, even just at a corner: 8 in 2D, 26
+    /// in 3D. This is classic Minesweeper.
+    #[default]
+    Moore,
+    /// Only the cells one step away along a single axis: 4 in 2D, 6 in 3D.
+    /// Numbers are smaller and harder to reason about, which makes for a
+    /// tougher variant.
+    Orthogonal,
+}
+
+impl Adjacency {
+    /// Returns on how many axes a neighbor may differ from a cell on a board
+    /// with `num_dimensions` axes. See `coordinates::get_neighbors_by_order`.
+    pub fn max_changed_axes(self, num_dimensions: usize) -> usize {
+        match self {
+            Adjacency::Moore => num_dimensions,
+            Adjacency::Orthogonal => 1,
+        }
+    }
+}
+
+/// What a flood fill does when it runs into a flagged cell.
+///
+/// A neighbor of a zero cell can never be a mine, so any flag a flood fill
+/// reaches is wrong. Rule variants differ on whether that matters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FloodPolicy {
+    /// Flags are walls: the fill leaves them in place and doesn't spread
+    /// through them, so a region the player fenced off with flags stays
+    /// hidden. This is the classic behavior.
+    #[default]
+    StopAtFlags,
+    /// The fill removes the (necessarily wrong) flags it reaches, reveals
+    /// those cells, and carries on through them.
+    IgnoreFlags,
+}
+
+/// An end-of-game breakdown of how well the player located the mines.
+///
+/// Every mine appears in exactly one of `flagged_correct` and `unflagged`;
+/// `wrong_flags` lists the flags that were placed on safe cells.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MineReport {
+    /// Mines the player correctly flagged.
+    pub flagged_correct: Vec<Coordinates>,
+    /// Mines the player never flagged (whether still hidden or revealed).
+    pub unflagged: Vec<Coordinates>,
+    /// Flags placed on cells that are not mines.
+    pub wrong_flags: Vec<Coordinates>,
+}
+
+/// What the player can see around a cell, counted by kind. See
+/// `Board::neighbor_summary`.
+///
+/// `hidden + flagged + revealed` is the number of neighbors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NeighborSummary {
+    /// Neighbors still covered, question marks included.
+    pub hidden: usize,
+    /// Neighbors carrying a flag.
+    pub flagged: usize,
+    /// Neighbors that have been revealed.
+    pub revealed: usize,
+    /// Revealed neighbors that turned out to be mines (e.g. after a loss).
+    /// Covered mines are not counted, so the summary gives nothing away.
+    pub mines_if_known: usize,
+}
 
 // The Board struct will represent the N-dimensional game board.
+#[derive(Clone)]
 pub struct Board {
     /// The dimensions of the board (e.g., `vec![10, 10]` for a 2D 10x10 board).
     dimensions: Vec<usize>,
 
-    /// The cells of the board, stored in a flat vector.
+    /// For every axis, how far apart in flat index two neighbors along it are:
+    /// 1 for the first axis, then the product of the sizes of the axes
+    /// before it. Kept in step with `dimensions`. See `index_of`.
+    strides: Vec<usize>,
+
+    /// The state of every cell, stored in a flat vector.
     /// The mapping from N-dimensional coordinates to a 1D index is a key part
     /// of this implementation.
-    pub cells: Vec<Cell>,
+    ///
+    /// A cell's contents live in `mines` and `counts` rather than next to
+    /// its state: a `Cell` with its `CellKind` takes 6 bytes, while a state,
+    /// a count and a mine bit take just over 3, which matters on the huge
+    /// boards that N dimensions make easy to create. See `cell_at` for the
+    /// familiar `Cell` view.
+    pub(crate) states: Vec<CellState>,
+
+    /// Which cells hold a mine, one bit per cell.
+    pub(crate) mines: Bitset,
+
+    /// Every safe cell's adjacent-mine count, in flat index order. Mines
+    /// have no count, so whatever is stored for them is never read. See
+    /// `calculate_adjacent_mines`.
+    pub(crate) counts: Vec<u16>,
 
     /// The total number of mines on the board.
     num_mines: usize,
+
+    /// The most cells a single reveal may open, or `None` for no limit.
+    /// This is a safety net against accidentally enormous boards, where one
+    /// click could otherwise keep the flood fill busy for a very long time.
+    reveal_limit: Option<usize>,
+
+    /// The order in which `cycle_mark` steps through the player's marks.
+    mark_cycle: MarkCycle,
+
+    /// Which cells count as neighbors. See `Adjacency`.
+    adjacency: Adjacency,
+
+    /// Whether flood fills stop at flags. See `FloodPolicy`.
+    flood_policy: FloodPolicy,
+
+    /// Whether revealing a zero cell opens its neighbors. See `set_cascade`.
+    cascade: bool,
+
+    /// The cells the player can't interact with, by flat index. See
+    /// `set_frozen`.
+    frozen: Bitset,
+
+    /// For every axis, whether its edges wrap around, or `None` if no axis
+    /// does (the common case, which takes the fast neighbor path). See
+    /// `set_wrapping_axes`.
+    wrap: Option<Vec<bool>>,
+
+    /// Custom display names for the axes, or `None` for the defaults
+    /// (x, y, z, w, ...). See `set_axis_labels`.
+    axis_labels: Option<Vec<String>>,
+
+    /// For every cell, the number of the reveal that uncovered it, or `None`
+    /// while it is unrevealed. See `revealed_at_move`.
+    revealed_at: Vec<Option<usize>>,
+
+    /// How many reveals have uncovered at least one cell so far. The next
+    /// such reveal is number `reveal_moves + 1`.
+    reveal_moves: usize,
 }
 
 impl Board {
@@ -33,190 +230,3726 @@ impl Board {
     ///
     /// * `dimensions` - A vector defining the size of each dimension.
     /// * `num_mines` - The number of mines to place.
-    pub fn new(dimensions: Vec<usize>, num_mines: usize) -> Self {
-        // Calculate the total number of cells.
-        let total_cells = dimensions.iter().product();
+    ///
+    /// # Errors
+    ///
+    /// * `BoardError::TooLarge` if the board would have more than
+    ///   `DEFAULT_MAX_CELLS` cells.
+    /// * `BoardError::TooManyMines` if there are more mines than cells.
+    pub fn new(dimensions: Vec<usize>, num_mines: usize) -> Result<Self, BoardError> {
+        Self::new_with_max_cells(dimensions, num_mines, DEFAULT_MAX_CELLS)
+    }
+
+    /// Creates a new board, refusing to create more than `max_cells` cells.
+    ///
+    /// This is `Board::new` with a custom size limit, e.g. a lower one for a
+    /// server accepting board sizes from untrusted players.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Board::new`, with `max_cells` as the size limit.
+    pub fn new_with_max_cells(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        max_cells: usize,
+    ) -> Result<Self, BoardError> {
+        Self::generate(dimensions, num_mines, max_cells, &mut rand::thread_rng())
+    }
+
+    /// Creates a new random board from a seed.
+    ///
+    /// The same dimensions, mine count and seed always produce the same
+    /// layout, which makes boards shareable and tests reproducible.
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - A vector defining the size of each dimension.
+    /// * `num_mines` - The number of mines to place.
+    /// * `seed` - Seed for the random number generator.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Board::new`.
+    pub fn new_seeded(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        Self::new_with_rng(dimensions, num_mines, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// Creates a new random board using the given random number generator.
+    ///
+    /// All random constructors funnel through here, so that seeded and
+    /// unseeded boards are built by exactly the same code, and are validated
+    /// by exactly the same checks. Any generator works, e.g. a seeded
+    /// `ChaCha8Rng` or a mock generator in tests; `new` passes
+    /// `rand::thread_rng()`.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Board::new`.
+    pub fn new_with_rng(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        rng: &mut impl RngCore,
+    ) -> Result<Self, BoardError> {
+        Self::generate(dimensions, num_mines, DEFAULT_MAX_CELLS, rng)
+    }
+
+    /// Creates a new random board of at most `max_cells` cells. This is the
+    /// shared body of `new_with_rng` and `new_with_max_cells`, which only
+    /// differ in the limit and the generator they pass.
+    fn generate(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        max_cells: usize,
+        rng: &mut impl RngCore,
+    ) -> Result<Self, BoardError> {
+        let total_cells = checked_cell_count(&dimensions, max_cells)?;
+        if num_mines > total_cells {
+            return Err(BoardError::TooManyMines {
+                requested: num_mines,
+                available: total_cells,
+            });
+        }
+
+        // Every cell is a candidate for a mine.
+        let candidates: Vec<usize> = (0..total_cells).collect();
+        let board = Self::generate_among(dimensions, &candidates, num_mines, rng);
+        Ok(board)
+    }
+
+    /// Creates a new random board, choosing mine positions as `placement`
+    /// says.
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - A vector defining the size of each dimension.
+    /// * `num_mines` - The number of mines to place.
+    /// * `placement` - How to choose mine positions. See `Placement`.
+    /// * `seed` - Seed for the random number generator.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Board::new`.
+    pub fn new_with_placement(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        placement: Placement,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        match placement {
+            Placement::Uniform => Self::new_with_rng(dimensions, num_mines, &mut rng),
+            Placement::Spread => {
+                // Start from an empty board of the right size (this also
+                // validates the arguments), then place the mines ourselves.
+                let mut board = Self::new_with_rng(dimensions, 0, &mut rng)?;
+                let total_cells = board.cell_count();
+                if num_mines > total_cells {
+                    return Err(BoardError::TooManyMines {
+                        requested: num_mines,
+                        available: total_cells,
+                    });
+                }
+                board.place_mines_spread(num_mines, &mut rng);
+                board.num_mines = num_mines;
+                board.calculate_adjacent_mines();
+                Ok(board)
+            }
+        }
+    }
+
+    /// Places `num_mines` mines on a mine-free board, keeping them apart.
+    ///
+    /// This is a greedy, "Poisson-disk-like" scheme on the grid: cells are
+    /// visited in random order, and a cell only gets a mine if none of its
+    /// neighbors has one yet. If that leaves mines over (the board is too
+    /// dense for all of them to be isolated), the rest go on the skipped
+    /// cells, still in random order.
+    fn place_mines_spread(&mut self, num_mines: usize, rng: &mut impl Rng) {
+        let mut order: Vec<usize> = (0..self.cell_count()).collect();
+        order.shuffle(rng);
+
+        let mut placed = 0;
+        let mut skipped = Vec::new();
+        for index in order {
+            if placed == num_mines {
+                break;
+            }
+            let touches_mine = self
+                .neighbor_indices(index)
+                .into_iter()
+                .any(|neighbor| self.mines.get(neighbor));
+            if touches_mine {
+                skipped.push(index);
+            } else {
+                self.mines.set(index, true);
+                placed += 1;
+            }
+        }
+        // If the loop above ran out of cells, `skipped` holds every cell
+        // that is still free.
+        for index in skipped.into_iter().take(num_mines - placed) {
+            self.mines.set(index, true);
+        }
+    }
 
-        // Create the cells.
-        let mut cells = vec![Cell::new(); total_cells];
+    /// Creates a new random board whose mines are only placed on the
+    /// `candidates` (flat indices).
+    ///
+    /// Restricting *where* mines may go is how all the "safe zone" style
+    /// generators are built; they only differ in which candidates they pass.
+    fn generate_among(
+        dimensions: Vec<usize>,
+        candidates: &[usize],
+        num_mines: usize,
+        rng: &mut impl Rng,
+    ) -> Self {
+        // Calculate the total number of cells. Every caller has already
+        // checked the dimensions against its size limit.
+        let total_cells = checked_cell_count(&dimensions, usize::MAX)
+            .expect("dimensions are validated before generating");
 
         // Place the mines.
-        Self::place_mines(&mut cells, num_mines);
+        let mut mines = Bitset::new(total_cells);
+        Self::place_mines(&mut mines, candidates, num_mines, rng);
+
+        Self::assemble(dimensions, mines)
+    }
 
+    /// Wraps a freshly generated mine layout into a board with default
+    /// settings, every cell hidden, and computes the adjacency counts.
+    ///
+    /// Every constructor ends here, so a new field only needs a default in
+    /// one place.
+    fn assemble(dimensions: Vec<usize>, mines: Bitset) -> Self {
+        let total_cells = mines.len();
         let mut board = Self {
+            strides: Self::strides_for(&dimensions),
             dimensions,
-            cells,
-            num_mines,
+            states: vec![CellState::Hidden; total_cells],
+            num_mines: mines.count_ones(),
+            mines,
+            counts: vec![0; total_cells],
+            reveal_limit: None,
+            mark_cycle: MarkCycle::default(),
+            adjacency: Adjacency::default(),
+            flood_policy: FloodPolicy::default(),
+            cascade: true,
+            frozen: Bitset::new(total_cells),
+            wrap: None,
+            axis_labels: None,
+            revealed_at: vec![None; total_cells],
+            reveal_moves: 0,
         };
-
         board.calculate_adjacent_mines();
+        debug_assert_eq!(board.verify_integrity(), Ok(()));
         board
     }
 
-    /// Calculates and sets the number of adjacent mines for each empty cell.
-    fn calculate_adjacent_mines(&mut self) {
-        for i in 0..self.cells.len() {
-            // We only need to calculate for empty cells
-            if self.cells[i].kind == CellKind::Mine {
-                continue;
-            }
+    /// Creates a board with mines at exactly the given coordinates.
+    ///
+    /// Random boards are great for playing, but tests, tutorials and puzzle
+    /// editors need to describe a precise layout. Duplicate coordinates are
+    /// only counted once, so `num_mines` always matches the real layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - A vector defining the size of each dimension.
+    /// * `mines` - The coordinates of every mine.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are too many cells to count in a `usize`.
+    pub fn from_mines(dimensions: Vec<usize>, mines: &[Coordinates]) -> Self {
+        let total_cells =
+            checked_cell_count(&dimensions, usize::MAX).expect("board dimensions overflow usize");
+        let mut bits = Bitset::new(total_cells);
+        for coords in mines {
+            bits.set(to_index(coords, &dimensions), true);
+        }
+        Self::from_mine_bitset(dimensions, &bits)
+    }
 
-            let coords = to_coords(i, &self.dimensions);
-            let neighbors = get_neighbors(&coords, &self.dimensions);
+    /// Creates a board from a mine bitset, indexed by flat index.
+    ///
+    /// A bitset is the most compact way to store or transmit a layout: one
+    /// bit per cell, which is exactly how the board itself stores its mines.
+    /// Together with `mine_bitset`, this lets callers keep huge layouts
+    /// around cheaply and only expand them into a playable `Board` (with
+    /// cell states and adjacency counts) when needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mines.len()` doesn't match the number of cells, or if
+    /// there are too many cells to count in a `usize`.
+    pub fn from_mine_bitset(dimensions: Vec<usize>, mines: &Bitset) -> Self {
+        let total_cells =
+            checked_cell_count(&dimensions, usize::MAX).expect("board dimensions overflow usize");
+        assert_eq!(mines.len(), total_cells, "bitset size must match the board");
+        Self::assemble(dimensions, mines.clone())
+    }
 
-            let mut mine_count = 0;
-            for neighbor_coords in neighbors {
-                let neighbor_index = to_index(&neighbor_coords, &self.dimensions);
-                if self.cells[neighbor_index].kind == CellKind::Mine {
-                    mine_count += 1;
-                }
-            }
+    /// Creates a random board whose 3BV is at least `min_3bv`.
+    ///
+    /// Random layouts are sometimes trivially easy; this generator simply
+    /// keeps rolling new layouts until one is hard enough. See
+    /// `compute_3bv` for what the number means (and why it is 2D-focused).
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - A vector defining the size of each dimension.
+    /// * `num_mines` - The number of mines to place.
+    /// * `min_3bv` - The minimum acceptable 3BV.
+    /// * `seed` - Seed for the random number generator, so the same arguments
+    ///   always produce the same board.
+    ///
+    /// # Errors
+    ///
+    /// * `BoardError::GenerationFailed` if no matching board was found within
+    ///   `MAX_GENERATION_ATTEMPTS` layouts.
+    /// * Any error `Board::new` can return.
+    pub fn new_with_min_3bv(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        min_3bv: usize,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        Self::generate_until(dimensions, num_mines, seed, |board| {
+            board.compute_3bv() >= min_3bv
+        })
+    }
+
+    /// Creates a random board that is guaranteed to contain at least one
+    /// zero cell (see `has_zero_cell`).
+    ///
+    /// A board without any zero cell can never cascade, so the player has to
+    /// open it one risky cell at a time. Rejecting such layouts guarantees
+    /// there is always an opening to be found.
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - A vector defining the size of each dimension.
+    /// * `num_mines` - The number of mines to place.
+    /// * `seed` - Seed for the random number generator.
+    ///
+    /// # Errors
+    ///
+    /// * `BoardError::GenerationFailed` if no matching board was found within
+    ///   `MAX_GENERATION_ATTEMPTS` layouts (e.g. because the board is too
+    ///   dense for any zero cell to exist).
+    /// * Any error `Board::new` can return.
+    pub fn new_with_zero_cell(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        Self::generate_until(dimensions, num_mines, seed, Board::has_zero_cell)
+    }
+
+    /// Creates a random board whose mines are spread out evenly enough that
+    /// no quadrant is denser than `max_density`.
+    ///
+    /// The "quadrants" are the 2^N boxes obtained by cutting every axis in
+    /// half (with an odd size, the extra row goes to the first half). A fully
+    /// random layout sometimes piles most mines into one corner, which makes
+    /// that corner a guessing game while the rest of the board is trivial.
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - A vector defining the size of each dimension.
+    /// * `num_mines` - The number of mines to place.
+    /// * `max_density` - The largest acceptable fraction of mines in any
+    ///   quadrant, from `0.0` to `1.0`. It should be comfortably above the
+    ///   board's overall density, or no layout will pass.
+    /// * `seed` - Seed for the random number generator.
+    ///
+    /// # Errors
+    ///
+    /// * `BoardError::GenerationFailed` if no matching board was found within
+    ///   `MAX_GENERATION_ATTEMPTS` layouts.
+    /// * Any error `Board::new` can return.
+    pub fn new_with_max_quadrant_density(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        max_density: f64,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        Self::generate_until(dimensions, num_mines, seed, |board| {
+            board.max_quadrant_density() <= max_density
+        })
+    }
+
+    /// Creates a random board that can be cleared without guessing.
+    ///
+    /// "Without guessing" means that after the first click, which opens the
+    /// best available zero region, the solver (see the `solver` module) can
+    /// deduce every remaining move. Pair it with `FirstClick::SafeRegion` so
+    /// that the player's first click is guaranteed to open a region too.
+    ///
+    /// Running the solver on every candidate makes this much slower than the
+    /// other generators. With the `parallel` feature enabled, candidates are
+    /// generated and solved on all CPU cores at once, and the remaining work
+    /// is cancelled as soon as a solvable board is found. Each attempt has its
+    /// own seed derived from `seed`, and the lowest-numbered solvable attempt
+    /// wins, so the result is the same board with or without the feature.
+    ///
+    /// # Errors
+    ///
+    /// * `BoardError::GenerationFailed` if none of `MAX_GENERATION_ATTEMPTS`
+    ///   layouts could be solved without guessing.
+    /// * Any error `Board::new` can return.
+    pub fn new_no_guess(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        // Validate once up front, so that the attempts themselves can't fail.
+        let total_cells = checked_cell_count(&dimensions, DEFAULT_MAX_CELLS)?;
+        if num_mines > total_cells {
+            return Err(BoardError::TooManyMines {
+                requested: num_mines,
+                available: total_cells,
+            });
+        }
+
+        let attempt = |attempt: usize| {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(attempt as u64));
+            Self::new_with_rng(dimensions.clone(), num_mines, &mut rng)
+                .ok()
+                .filter(|board| guesses_needed(board) <= 1)
+        };
 
-            // Update the cell's kind with the mine count
-            if let CellKind::Empty { adjacent_mines } = &mut self.cells[i].kind {
-                *adjacent_mines = mine_count;
+        #[cfg(feature = "parallel")]
+        let found = {
+            use rayon::prelude::*;
+            (0..MAX_GENERATION_ATTEMPTS)
+                .into_par_iter()
+                .find_map_first(attempt)
+        };
+        #[cfg(not(feature = "parallel"))]
+        let found = (0..MAX_GENERATION_ATTEMPTS).find_map(attempt);
+
+        found.ok_or(BoardError::GenerationFailed {
+            attempts: MAX_GENERATION_ATTEMPTS,
+        })
+    }
+
+    /// Generates seeded random boards until one satisfies `accept`.
+    ///
+    /// This is the shared engine behind the filtering generators. Up to
+    /// `MAX_GENERATION_ATTEMPTS` layouts are tried before giving up.
+    fn generate_until(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        seed: u64,
+        accept: impl Fn(&Board) -> bool,
+    ) -> Result<Self, BoardError> {
+        // One generator is shared by all attempts, so each attempt sees a
+        // different layout while the whole sequence stays reproducible.
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..MAX_GENERATION_ATTEMPTS {
+            let board = Self::new_with_rng(dimensions.clone(), num_mines, &mut rng)?;
+            if accept(&board) {
+                return Ok(board);
             }
         }
+        Err(BoardError::GenerationFailed {
+            attempts: MAX_GENERATION_ATTEMPTS,
+        })
     }
 
-    /// Places mines randomly on the board.
-    fn place_mines(cells: &mut Vec<Cell>, num_mines: usize) {
-        let mut rng = rand::thread_rng();
-        let mine_indices = (0..cells.len()).collect::<Vec<usize>>();
-        let chosen_indices = mine_indices.choose_multiple(&mut rng, num_mines);
+    /// Creates a random board where no mine lands on any excluded coordinate.
+    ///
+    /// This is meant for teaching: a tutorial can promise the player that a
+    /// certain area is safe to explore.
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - A vector defining the size of each dimension.
+    /// * `num_mines` - The number of mines to place.
+    /// * `exclude` - Coordinates that must stay mine-free.
+    /// * `seed` - Seed for the random number generator.
+    ///
+    /// # Errors
+    ///
+    /// * `BoardError::TooManyMines` if there are fewer non-excluded cells
+    ///   than mines to place.
+    /// * `BoardError::TooLarge` as for `Board::new`.
+    /// * Any error from `validate_coords` for an excluded coordinate that
+    ///   isn't on the board.
+    pub fn new_with_exclusions(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        exclude: &[Coordinates],
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        let total_cells = checked_cell_count(&dimensions, DEFAULT_MAX_CELLS)?;
+        let mut excluded = vec![false; total_cells];
+        for coords in exclude {
+            // Unchecked, `[x, y]` on a 3D board would quietly exclude some
+            // other cell, and a coordinate past the edge could land on a
+            // cell of the next row.
+            check_coords(coords, &dimensions)?;
+            excluded[to_index(coords, &dimensions)] = true;
+        }
 
-        for &index in chosen_indices {
-            cells[index].kind = CellKind::Mine;
+        let candidates: Vec<usize> = (0..total_cells).filter(|&i| !excluded[i]).collect();
+        if candidates.len() < num_mines {
+            return Err(BoardError::TooManyMines {
+                requested: num_mines,
+                available: candidates.len(),
+            });
         }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        Ok(Self::generate_among(
+            dimensions,
+            &candidates,
+            num_mines,
+            &mut rng,
+        ))
     }
 
-    /// Toggles a flag on a cell.
+    /// Creates a random board whose outer shell is free of mines: no cell
+    /// with a coordinate of 0 or `size - 1` on any axis gets one.
+    ///
+    /// This is for variants where the edge of the board is always safe to
+    /// explore. Only the interior holds mines, so every axis needs to be at
+    /// least 3 long for there to be any room at all.
     ///
     /// # Arguments
     ///
-    /// * `coords` - The coordinates of the cell to toggle the flag on.
-    pub fn toggle_flag(&mut self, coords: &crate::coordinates::Coordinates) {
-        let index = to_index(coords, &self.dimensions);
-        if let Some(cell) = self.cells.get_mut(index) {
-            match cell.state {
-                CellState::Hidden => cell.state = CellState::Flagged,
-                CellState::Flagged => cell.state = CellState::Hidden,
-                CellState::Revealed => (),
-            }
+    /// * `dimensions` - A vector defining the size of each dimension.
+    /// * `num_mines` - The number of mines to place.
+    /// * `seed` - Seed for the random number generator.
+    ///
+    /// # Errors
+    ///
+    /// * `BoardError::TooManyMines` if there are fewer interior cells than
+    ///   mines to place.
+    /// * `BoardError::TooLarge` as for `Board::new`.
+    pub fn new_safe_border(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        let total_cells = checked_cell_count(&dimensions, DEFAULT_MAX_CELLS)?;
+        let candidates: Vec<usize> = (0..total_cells)
+            .filter(|&i| {
+                let coords = to_coords(i, &dimensions);
+                coords
+                    .iter()
+                    .zip(&dimensions)
+                    .all(|(&c, &size)| c > 0 && c + 1 < size)
+            })
+            .collect();
+        if candidates.len() < num_mines {
+            return Err(BoardError::TooManyMines {
+                requested: num_mines,
+                available: candidates.len(),
+            });
         }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        Ok(Self::generate_among(
+            dimensions,
+            &candidates,
+            num_mines,
+            &mut rng,
+        ))
     }
 
-    /// Reveals a cell.
+    /// Creates a random board where each cell's chance of getting a mine is
+    /// proportional to `weight(coords)`, e.g. to pack mines towards the
+    /// center.
+    ///
+    /// Mines are drawn one after another without replacement, so no cell
+    /// gets two. A weight of zero (or a negative, infinite or NaN one) keeps
+    /// a cell mine-free; all positive weights only matter relative to each
+    /// other. `weight` is called once per cell, in flat index order.
     ///
     /// # Arguments
     ///
-    /// * `coords` - The coordinates of the cell to reveal.
+    /// * `dimensions` - A vector defining the size of each dimension.
+    /// * `num_mines` - The number of mines to place.
+    /// * `weight` - How likely each cell is to get a mine.
+    /// * `seed` - Seed for the random number generator.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// * `true` if a mine was revealed, `false` otherwise.
-    pub fn reveal(&mut self, coords: &crate::coordinates::Coordinates) -> bool {
-        let index = to_index(coords, &self.dimensions);
-
-        // Can't reveal a flagged or already revealed cell
-        if self.cells[index].state == CellState::Flagged
-            || self.cells[index].state == CellState::Revealed
-        {
-            return false;
+    /// * `BoardError::TooManyMines` if fewer cells have a positive weight
+    ///   than there are mines to place.
+    /// * `BoardError::TooLarge` as for `Board::new`.
+    pub fn new_weighted(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        weight: impl Fn(&Coordinates) -> f64,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        let total_cells = checked_cell_count(&dimensions, DEFAULT_MAX_CELLS)?;
+        let candidates: Vec<(usize, f64)> = (0..total_cells)
+            .map(|i| (i, weight(&to_coords(i, &dimensions))))
+            .filter(|&(_, w)| w.is_finite() && w > 0.0)
+            .collect();
+        if candidates.len() < num_mines {
+            return Err(BoardError::TooManyMines {
+                requested: num_mines,
+                available: candidates.len(),
+            });
         }
 
-        self.cells[index].state = CellState::Revealed;
-
-        match self.cells[index].kind {
-            CellKind::Mine => true,
-            CellKind::Empty { adjacent_mines } => {
-                if adjacent_mines == 0 {
-                    // If the cell is empty and has no adjacent mines, reveal all its neighbors
-                    let neighbors = get_neighbors(coords, &self.dimensions);
-                    for neighbor_coords in neighbors {
-                        self.reveal(&neighbor_coords);
-                    }
-                }
-                false
-            }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut mines = Bitset::new(total_cells);
+        let chosen = candidates
+            .choose_multiple_weighted(&mut rng, num_mines, |&(_, w)| w)
+            .expect("only finite, positive weights are left");
+        for &(index, _) in chosen {
+            mines.set(index, true);
         }
+        Ok(Self::assemble(dimensions, mines))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::cell::{Cell, CellKind, CellState};
+    /// Returns the dimensions of the board.
+    pub fn dimensions(&self) -> &[usize] {
+        &self.dimensions
+    }
 
-    #[test]
-    fn test_calculate_adjacent_mines_2d() {
-        let dimensions = vec![3, 3];
-        let total_cells = 9;
-        let mut cells = vec![Cell::new(); total_cells];
+    /// Returns the size of the board along `axis`, or `None` if the board
+    /// has no such axis.
+    pub fn dimension(&self, axis: usize) -> Option<usize> {
+        self.dimensions.get(axis).copied()
+    }
 
-        // Place mines at (0,0) [index 0] and (2,2) [index 8]
-        cells[0].kind = CellKind::Mine;
-        cells[8].kind = CellKind::Mine;
+    /// Gives the board a new shape with the same number of cells, e.g. turns
+    /// a 4x4 board into a 2x8 one.
+    ///
+    /// Every cell keeps its flat index, and with it its mine and its state;
+    /// only the coordinates (and therefore the neighborhoods) change, so all
+    /// the numbers are recomputed. Axis labels and wrapping settings are
+    /// dropped if the number of axes changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BoardError::CellCountMismatch` (and leaves the board
+    /// untouched) if `new_dimensions` doesn't describe exactly as many cells
+    /// as the board has.
+    pub fn reshape(&mut self, new_dimensions: Vec<usize>) -> Result<(), BoardError> {
+        let actual = new_dimensions
+            .iter()
+            .try_fold(1_usize, |acc, &size| acc.checked_mul(size));
+        if actual != Some(self.cell_count()) {
+            return Err(BoardError::CellCountMismatch {
+                expected: self.cell_count(),
+                actual,
+            });
+        }
 
-        let mut board = Board {
-            dimensions,
-            cells,
-            num_mines: 2,
-        };
+        if new_dimensions.len() != self.dimensions.len() {
+            self.axis_labels = None;
+            self.wrap = None;
+        }
+        self.strides = Self::strides_for(&new_dimensions);
+        self.dimensions = new_dimensions;
+        self.calculate_adjacent_mines();
+        Ok(())
+    }
 
-        board.calculate_adjacent_mines();
+    /// Computes the `strides` of a board with the given dimensions.
+    fn strides_for(dimensions: &[usize]) -> Vec<usize> {
+        let mut stride = 1;
+        dimensions
+            .iter()
+            .map(|&size| {
+                let this = stride;
+                stride *= size;
+                this
+            })
+            .collect()
+    }
 
-        // Check adjacent mine counts for a few cells
-        // Cell (1,0) [index 1] should have 1 neighbor mine.
-        if let CellKind::Empty { adjacent_mines } = board.cells[1].kind {
-            assert_eq!(adjacent_mines, 1);
-        } else {
-            panic!("Cell (1,0) should be empty");
+    /// Converts coordinates to a flat cell index.
+    ///
+    /// Gives the same answer as `coordinates::to_index`, but uses strides
+    /// worked out once when the board was built instead of recomputing them
+    /// on every call, which adds up in loops over every cell. Like
+    /// `to_index`, it trusts its input: check coordinates from outside with
+    /// `validate_coords` first.
+    pub fn index_of(&self, coords: &Coordinates) -> usize {
+        coords
+            .iter()
+            .zip(&self.strides)
+            .map(|(&coord, &stride)| coord * stride)
+            .sum()
+    }
+
+    /// Converts a flat cell index back to coordinates.
+    ///
+    /// The cached-strides counterpart of `coordinates::to_coords`, which it
+    /// matches for every index, including its handling of indices past the
+    /// end.
+    pub fn coords_of(&self, index: usize) -> Coordinates {
+        let mut rest = index;
+        let mut coords = vec![0; self.strides.len()];
+        for (axis, &stride) in self.strides.iter().enumerate().rev() {
+            coords[axis] = rest / stride;
+            rest %= stride;
         }
+        coords
+    }
 
-        // Cell (0,1) [index 3] should have 1 neighbor mine.
-        if let CellKind::Empty { adjacent_mines } = board.cells[3].kind {
-            assert_eq!(adjacent_mines, 1);
-        } else {
-            panic!("Cell (0,1) should be empty");
+    /// Returns the total number of cells on the board.
+    pub fn cell_count(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Returns the cell at flat index `index` (see `index_of`): its state,
+    /// and whether it is a mine or how many mines it touches.
+    ///
+    /// The board doesn't store `Cell`s (see the `states` field), so this
+    /// puts one together; it is cheap, but it is a copy, and changing it
+    /// doesn't change the board. Like `is_mine`, this reads the answer key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not smaller than `cell_count()`.
+    pub fn cell_at(&self, index: usize) -> Cell {
+        Cell {
+            state: self.states[index].clone(),
+            kind: self.kind_at(index),
         }
+    }
 
-        // Cell (1,1) [index 4] should have 2 neighbor mines.
-        if let CellKind::Empty { adjacent_mines } = board.cells[4].kind {
-            assert_eq!(adjacent_mines, 2);
+    /// Returns every cell of the board, in flat index order. See `cell_at`.
+    pub fn cells(&self) -> impl ExactSizeIterator<Item = Cell> + '_ {
+        (0..self.cell_count()).map(|index| self.cell_at(index))
+    }
+
+    /// Returns what the cell at flat index `index` holds.
+    pub(crate) fn kind_at(&self, index: usize) -> CellKind {
+        if self.mines.get(index) {
+            CellKind::Mine
         } else {
-            panic!("Cell (1,1) should be empty");
+            CellKind::Empty {
+                adjacent_mines: self.counts[index],
+            }
         }
+    }
 
-        // Ensure mine cells are untouched
-        assert_eq!(board.cells[0].kind, CellKind::Mine);
-        assert_eq!(board.cells[8].kind, CellKind::Mine);
+    /// Returns `true` if the cell at flat index `index` is safe and touches
+    /// no mine: the cells that start a cascade.
+    pub(crate) fn is_zero_at(&self, index: usize) -> bool {
+        !self.mines.get(index) && self.counts[index] == 0
     }
 
-    #[test]
-    fn test_toggle_flag() {
-        let mut board = Board::new(vec![2, 2], 0);
-        let coords = vec![0, 0];
+    /// Returns the total number of mines on the board.
+    pub fn num_mines(&self) -> usize {
+        self.num_mines
+    }
 
-        // Initially hidden
-        assert_eq!(board.cells[0].state, CellState::Hidden);
+    /// Returns how many cells are not mines, i.e. how many cells must be
+    /// revealed to win.
+    pub fn safe_cells(&self) -> usize {
+        self.cell_count() - self.num_mines
+    }
 
-        // Toggle to flagged
-        board.toggle_flag(&coords);
-        assert_eq!(board.cells[0].state, CellState::Flagged);
+    /// Returns `true` if both boards have the same dimensions and the same
+    /// mine positions, regardless of which cells are revealed or flagged.
+    ///
+    /// This answers "did the generator produce the same puzzle?", which is
+    /// what matters when comparing layouts; the player's progress is noise.
+    pub fn same_layout(&self, other: &Board) -> bool {
+        self.dimensions == other.dimensions && self.mine_bitset() == other.mine_bitset()
+    }
 
-        // Toggle back to hidden
-        board.toggle_flag(&coords);
-        assert_eq!(board.cells[0].state, CellState::Hidden);
+    /// Returns the state of the cell at `coords`, or `None` if the coordinates
+    /// are not on the board.
+    pub fn cell_state(&self, coords: &Coordinates) -> Option<CellState> {
+        self.checked_index(coords)
+            .map(|index| self.states[index].clone())
     }
 
-    #[test]
+    /// Returns `true` if the cell at `coords` carries a flag.
+    ///
+    /// Coordinates outside the board are simply not flagged.
+    pub fn is_flagged(&self, coords: &Coordinates) -> bool {
+        self.cell_state(coords) == Some(CellState::Flagged)
+    }
+
+    /// Returns `true` if the cell at `coords` has been revealed.
+    ///
+    /// Coordinates outside the board are simply not revealed.
+    pub fn is_revealed(&self, coords: &Coordinates) -> bool {
+        self.cell_state(coords) == Some(CellState::Revealed)
+    }
+
+    /// Freezes or thaws the cell at `coords`.
+    ///
+    /// A frozen cell doesn't respond to the player: it can't be revealed,
+    /// flagged or marked, and flood fills flow around it instead of opening
+    /// it. Tutorials use this to keep the learner's attention on one part of
+    /// the board. Note that a game can't be won while a safe cell is frozen
+    /// and still hidden, so thaw the cells when the lesson moves on.
+    ///
+    /// # Errors
+    ///
+    /// Any error from `validate_coords`, in which case nothing changes.
+    pub fn set_frozen(&mut self, coords: &Coordinates, frozen: bool) -> Result<(), BoardError> {
+        self.validate_coords(coords)?;
+        self.frozen.set(self.index_of(coords), frozen);
+        Ok(())
+    }
+
+    /// Returns `true` if the cell at `coords` is frozen (see `set_frozen`).
+    ///
+    /// Coordinates outside the board are simply not frozen.
+    pub fn is_frozen(&self, coords: &Coordinates) -> bool {
+        self.checked_index(coords)
+            .is_some_and(|index| self.frozen.get(index))
+    }
+
+    /// Returns whether the cell at `coords` holds a mine, or `None` if the
+    /// coordinates are not on the board.
+    ///
+    /// This reads the answer key directly and changes nothing, so it is meant
+    /// for editors, tools and tests; a player-facing front-end should only
+    /// show what `cell_state` allows.
+    pub fn is_mine(&self, coords: &Coordinates) -> Option<bool> {
+        self.checked_index(coords)
+            .map(|index| self.mines.get(index))
+    }
+
+    /// Converts `coords` to a flat index, or returns `None` if they are not on
+    /// the board.
+    ///
+    /// `to_index` trusts its input: coordinates with the wrong number of
+    /// axes, or beyond an edge, silently map to the *wrong* cell (or past the
+    /// last one). Every query that accepts coordinates from outside the
+    /// board should go through this check instead.
+    fn checked_index(&self, coords: &Coordinates) -> Option<usize> {
+        self.validate_coords(coords).ok()?;
+        Some(self.index_of(coords))
+    }
+
+    /// Checks that `coords` name a cell on this board.
+    ///
+    /// `to_index` trusts its input: coordinates with the wrong number of
+    /// components still produce an index, just the wrong one. This catches
+    /// such mistakes (e.g. passing `[x, y]` to a 3D board) at the boundary,
+    /// before they turn into confusing behavior far from the actual bug.
+    ///
+    /// # Errors
+    ///
+    /// * `BoardError::WrongCoordinateCount` if `coords` has a different
+    ///   number of components than the board has dimensions.
+    /// * `BoardError::OutOfBounds` if any component is beyond the edge.
+    pub fn validate_coords(&self, coords: &Coordinates) -> Result<(), BoardError> {
+        check_coords(coords, &self.dimensions)
+    }
+
+    /// Returns every cell's adjacent-mine count, in flat index order.
+    ///
+    /// Mines are reported as `MINE_SENTINEL`. Reveal state is ignored, so this
+    /// exposes the full answer key; it is meant for analysis tools and
+    /// heatmaps, not for showing to a player mid-game.
+    pub fn adjacency_grid(&self) -> Vec<u16> {
+        (0..self.cell_count())
+            .map(|index| match self.kind_at(index) {
+                CellKind::Mine => MINE_SENTINEL,
+                CellKind::Empty { adjacent_mines } => adjacent_mines,
+            })
+            .collect()
+    }
+
+    /// Recomputes every adjacent-mine count the slow way, by comparing every
+    /// cell against every mine, and returns them like `adjacency_grid`.
+    ///
+    /// Two cells are neighbors when they differ by at most one step on every
+    /// axis (measured around the board on wrapping axes), and on no more
+    /// axes than the adjacency mode allows. This shares no code with the
+    /// offset-based neighbor walk that `calculate_adjacent_mines` uses, so it
+    /// serves as a reference implementation: when the two disagree, the fast
+    /// path has a bug. It takes time proportional to cells times mines, so
+    /// keep it to tests and debugging.
+    pub fn brute_force_adjacency(&self) -> Vec<u16> {
+        let wrap = self.wrapping_axes();
+        let max_changed_axes = self.adjacency.max_changed_axes(self.dimensions.len());
+        let mines: Vec<Coordinates> = (0..self.cell_count())
+            .filter(|&index| self.mines.get(index))
+            .map(|index| self.coords_of(index))
+            .collect();
+
+        let is_neighbor = |a: &Coordinates, b: &Coordinates| {
+            let mut changed = 0;
+            for (axis, (&x, &y)) in a.iter().zip(b).enumerate() {
+                let mut step = x.abs_diff(y);
+                if wrap[axis] {
+                    step = step.min(self.dimensions[axis] - step);
+                }
+                if step > 1 {
+                    return false;
+                }
+                changed += step;
+            }
+            changed > 0 && changed <= max_changed_axes
+        };
+
+        (0..self.cell_count())
+            .map(|index| {
+                if self.mines.get(index) {
+                    return MINE_SENTINEL;
+                }
+                let coords = self.coords_of(index);
+                saturating_count(
+                    mines
+                        .iter()
+                        .filter(|mine| is_neighbor(&coords, mine))
+                        .count(),
+                )
+            })
+            .collect()
+    }
+
+    /// Classifies every mine, and every flag on a safe cell. See `MineReport`.
+    ///
+    /// This reads the hidden layout, so it is meant for the end of a game,
+    /// when the board is shown to the player anyway.
+    pub fn mine_report(&self) -> MineReport {
+        let mut report = MineReport::default();
+        for (index, state) in self.states.iter().enumerate() {
+            let flagged = *state == CellState::Flagged;
+            let list = match (self.mines.get(index), flagged) {
+                (true, true) => &mut report.flagged_correct,
+                (true, false) => &mut report.unflagged,
+                (false, true) => &mut report.wrong_flags,
+                (false, false) => continue,
+            };
+            list.push(self.coords_of(index));
+        }
+        report
+    }
+
+    /// Counts the mines inside an N-dimensional box.
+    ///
+    /// The box runs from `min` to `max` *inclusive* on every axis, so
+    /// `mines_in_region(&vec![0, 0], &vec![1, 1])` looks at a 2x2 square.
+    /// Parts of the box outside the board are simply ignored, and a box with
+    /// `min > max` on some axis is empty.
+    pub fn mines_in_region(&self, min: &Coordinates, max: &Coordinates) -> usize {
+        (0..self.cell_count())
+            .filter(|&index| self.mines.get(index))
+            .filter(|&index| {
+                let coords = self.coords_of(index);
+                coords
+                    .iter()
+                    .zip(min.iter().zip(max))
+                    .all(|(c, (lo, hi))| lo <= c && c <= hi)
+            })
+            .count()
+    }
+
+    /// Returns the fraction of mines among the cells within `radius` steps of
+    /// `coords` on every axis, the cell itself included.
+    ///
+    /// This is a balancing aid: a value far above the board's overall
+    /// density (`num_mines / cells`) marks a cluster of mines, and one far
+    /// below marks a suspiciously easy area. The window is clipped to the
+    /// board, so cells near an edge are judged on fewer cells. See
+    /// `coordinates::get_neighbors_radius`.
+    pub fn local_density(&self, coords: &Coordinates, radius: usize) -> f64 {
+        let mut window = get_neighbors_radius(coords, &self.dimensions, radius);
+        window.push(coords.clone());
+        let mines = window
+            .iter()
+            .filter(|cell| self.mines.get(self.index_of(cell)))
+            .count();
+        mines as f64 / window.len() as f64
+    }
+
+    /// Returns, for every cell (by flat index), how many steps away the
+    /// nearest mine is, counting diagonal steps as one (the Chebyshev
+    /// distance). Mines themselves report 0, and their neighbors 1.
+    ///
+    /// This is a breadth-first search outwards from all mines at once, so it
+    /// takes time proportional to the number of cells, whatever the number
+    /// of mines. It measures plain distance on the grid: the board's
+    /// adjacency mode and wrapping edges are not taken into account. On a
+    /// board without mines, every cell reports `usize::MAX`.
+    pub fn nearest_mine_distance(&self) -> Vec<usize> {
+        let mut distances = vec![usize::MAX; self.cell_count()];
+        let mut queue = VecDeque::new();
+        for (index, distance) in distances.iter_mut().enumerate() {
+            if self.mines.get(index) {
+                *distance = 0;
+                queue.push_back(index);
+            }
+        }
+
+        while let Some(index) = queue.pop_front() {
+            let next = distances[index] + 1;
+            for neighbor in get_neighbor_indices(index, &self.dimensions) {
+                if distances[neighbor] == usize::MAX {
+                    distances[neighbor] = next;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Returns the mine density of the densest quadrant. See
+    /// `new_with_max_quadrant_density` for what the quadrants are.
+    fn max_quadrant_density(&self) -> f64 {
+        let axes = self.dimensions.len();
+        let mut densest: f64 = 0.0;
+
+        // Each bit of `mask` picks the first (0) or second (1) half of one
+        // axis, so the masks enumerate every quadrant exactly once.
+        for mask in 0..1_usize << axes {
+            let mut min = Vec::with_capacity(axes);
+            let mut max = Vec::with_capacity(axes);
+            for (axis, &size) in self.dimensions.iter().enumerate() {
+                let split = size.div_ceil(2);
+                if mask & (1 << axis) == 0 {
+                    min.push(0);
+                    max.push(split);
+                } else {
+                    min.push(split);
+                    max.push(size);
+                }
+            }
+
+            // `max` is exclusive here; an axis of size 1 has an empty
+            // second half, and so does every quadrant that uses it.
+            let cells: usize = min.iter().zip(&max).map(|(lo, hi)| hi - lo).product();
+            if cells == 0 {
+                continue;
+            }
+            let last: Coordinates = max.iter().map(|hi| hi - 1).collect();
+            let density = self.mines_in_region(&min, &last) as f64 / cells as f64;
+            densest = densest.max(density);
+        }
+        densest
+    }
+
+    /// Returns `true` if any empty cell has zero adjacent mines.
+    ///
+    /// Only zero cells start a cascade, so a board without one offers the
+    /// player no opening at all.
+    pub fn has_zero_cell(&self) -> bool {
+        (0..self.cell_count()).any(|index| self.is_zero_at(index))
+    }
+
+    /// Sets the most cells a single reveal may open, or `None` for no limit.
+    ///
+    /// When a flood fill reaches the limit, it stops and leaves the rest of
+    /// the region hidden; `try_reveal` reports this as an error.
+    pub fn set_reveal_limit(&mut self, limit: Option<usize>) {
+        self.reveal_limit = limit;
+    }
+
+    /// Returns the most cells a single reveal may open, or `None` for no limit.
+    pub fn reveal_limit(&self) -> Option<usize> {
+        self.reveal_limit
+    }
+
+    /// Sets the order used by `cycle_mark`. See `MarkCycle`.
+    pub fn set_mark_cycle(&mut self, cycle: MarkCycle) {
+        self.mark_cycle = cycle;
+    }
+
+    /// Returns the order used by `cycle_mark`.
+    pub fn mark_cycle(&self) -> MarkCycle {
+        self.mark_cycle
+    }
+
+    /// Sets which cells count as neighbors, and recomputes every number to
+    /// match. See `Adjacency`.
+    ///
+    /// This is meant to be called on a fresh board, before play starts:
+    /// numbers the player has already seen change meaning.
+    pub fn set_adjacency(&mut self, adjacency: Adjacency) {
+        self.adjacency = adjacency;
+        self.calculate_adjacent_mines();
+    }
+
+    /// Returns which cells count as neighbors on this board.
+    pub fn adjacency(&self) -> Adjacency {
+        self.adjacency
+    }
+
+    /// Sets what flood fills do with the flags they reach. See
+    /// `FloodPolicy`.
+    pub fn set_flood_policy(&mut self, policy: FloodPolicy) {
+        self.flood_policy = policy;
+    }
+
+    /// Returns what flood fills do with the flags they reach.
+    pub fn flood_policy(&self) -> FloodPolicy {
+        self.flood_policy
+    }
+
+    /// Turns flood fills on (the default) or off.
+    ///
+    /// With cascading off, every reveal opens exactly the clicked cell, even
+    /// a zero: the player has to uncover the cells around it one by one.
+    /// Some variants play this way to make every cell a decision. Chords
+    /// and the other multi-cell reveals still open each cell they target.
+    pub fn set_cascade(&mut self, cascade: bool) {
+        self.cascade = cascade;
+    }
+
+    /// Returns whether revealing a zero cell opens its neighbors too.
+    pub fn cascade(&self) -> bool {
+        self.cascade
+    }
+
+    /// Chooses, for every axis, whether its edges wrap around.
+    ///
+    /// On a wrapping axis, the cells at the two ends are neighbors, as on a
+    /// torus; wrapping only some axes gives a cylinder-like board (e.g.
+    /// `vec![true, false]` wraps horizontally but not vertically). Every
+    /// number is recomputed to match. Like `set_adjacency`, this is meant to
+    /// be called before play starts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't exactly one entry per dimension.
+    pub fn set_wrapping_axes(&mut self, wrap: Vec<bool>) {
+        assert_eq!(
+            wrap.len(),
+            self.dimensions.len(),
+            "need exactly one wrap setting per dimension"
+        );
+        self.wrap = wrap.contains(&true).then_some(wrap);
+        self.calculate_adjacent_mines();
+    }
+
+    /// Returns, for every axis, whether its edges wrap around.
+    pub fn wrapping_axes(&self) -> Vec<bool> {
+        match &self.wrap {
+            Some(wrap) => wrap.clone(),
+            None => vec![false; self.dimensions.len()],
+        }
+    }
+
+    /// Gives every axis a display name, such as `"row"` or `"time"`.
+    ///
+    /// The labels are used wherever the board shows coordinates to people:
+    /// in layer headers when rendering, and by `format_coords`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't exactly one label per dimension.
+    pub fn set_axis_labels(&mut self, labels: Vec<String>) {
+        assert_eq!(
+            labels.len(),
+            self.dimensions.len(),
+            "need exactly one label per dimension"
+        );
+        self.axis_labels = Some(labels);
+    }
+
+    /// Returns the display name of every axis: the custom labels if any were
+    /// set, `x`, `y`, `z`, `w`, ... otherwise.
+    pub fn axis_labels(&self) -> Vec<String> {
+        match &self.axis_labels {
+            Some(labels) => labels.clone(),
+            None => (0..self.dimensions.len()).map(default_axis_label).collect(),
+        }
+    }
+
+    /// Formats `coords` for people, using the board's axis labels, e.g.
+    /// `"x=3, y=4, z=1"`. See `coordinates::format_labeled`.
+    pub fn format_coords(&self, coords: &Coordinates) -> String {
+        format_labeled(coords, &self.axis_labels())
+    }
+
+    /// Returns the number of the reveal that uncovered the cell at `coords`,
+    /// or `None` if it is unrevealed (or not on the board).
+    ///
+    /// Reveals are numbered from 1, counting only those that uncovered at
+    /// least one cell. For a game played through `Game` that is every move
+    /// that opened something, so the numbers line up with the player's
+    /// moves. All cells opened by one flood fill share its number, which
+    /// makes this handy for "reveal order" heatmaps and replays.
+    ///
+    /// Snapshots don't store these numbers, so a loaded board reports
+    /// `None` for the cells that were revealed before saving.
+    pub fn revealed_at_move(&self, coords: &Coordinates) -> Option<usize> {
+        self.revealed_at[self.checked_index(coords)?]
+    }
+
+    /// Counts the cells of each kind, as `(mines, empty cells)`, by walking
+    /// the whole board.
+    ///
+    /// This is a debugging aid: unlike `num_mines`, which is bookkeeping, it
+    /// looks at what is actually on the board, so comparing the two catches
+    /// generators that place the wrong number of mines. Debug builds check
+    /// that comparison right here.
+    pub fn kind_counts(&self) -> (usize, usize) {
+        let mines = self.mines.count_ones();
+        debug_assert_eq!(mines, self.num_mines, "mine count out of sync");
+        (mines, self.cell_count() - mines)
+    }
+
+    /// Checks that the board is internally consistent.
+    ///
+    /// Two things are checked: the number of cells holding a mine matches
+    /// `num_mines` (a generator that put two mines on the same cell would
+    /// come up short), and every empty cell's adjacent mine count matches the
+    /// mines actually around it, under the board's current adjacency and
+    /// wrapping. Debug builds run this on every newly created board.
+    ///
+    /// Like `kind_counts`, this walks the whole board, so it is meant for
+    /// tests, debugging and validating boards from untrusted sources rather
+    /// than for every move.
+    ///
+    /// # Errors
+    ///
+    /// * `BoardError::MineCountMismatch` if the mine count is off.
+    /// * `BoardError::AdjacentCountMismatch` for the first cell (in flat
+    ///   index order) whose count is wrong.
+    pub fn verify_integrity(&self) -> Result<(), BoardError> {
+        let mines = self.mine_bitset();
+        let actual = mines.count_ones();
+        if actual != self.num_mines {
+            return Err(BoardError::MineCountMismatch {
+                expected: self.num_mines,
+                actual,
+            });
+        }
+
+        let geometry = self.geometry();
+        for (index, &adjacent_mines) in self.counts.iter().enumerate() {
+            if !mines.get(index) {
+                let expected = count_adjacent_mines(index, &geometry, &mines);
+                if adjacent_mines != expected {
+                    return Err(BoardError::AdjacentCountMismatch {
+                        coords: self.coords_of(index),
+                        expected,
+                        actual: adjacent_mines,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of cells that have been revealed.
+    pub fn revealed_count(&self) -> usize {
+        self.states
+            .iter()
+            .filter(|&state| *state == CellState::Revealed)
+            .count()
+    }
+
+    /// Returns the number of cells currently carrying a flag.
+    pub fn flag_count(&self) -> usize {
+        self.states
+            .iter()
+            .filter(|&state| *state == CellState::Flagged)
+            .count()
+    }
+
+    /// Returns `true` if there are more flags on the board than mines.
+    ///
+    /// At that point at least one flag must be wrong, whatever the layout,
+    /// so a UI can warn the player without giving anything away.
+    pub fn over_flagged(&self) -> bool {
+        self.flag_count() > self.num_mines
+    }
+
+    /// Returns the coordinates of every neighbor of `coords` on this board.
+    ///
+    /// This is the one place that decides what "adjacent" means for the
+    /// board. Prefer it over calling `coordinates::get_neighbors` directly:
+    /// it can't be handed the wrong dimensions, and every board feature that
+    /// goes through it agrees on the same neighborhood.
+    pub fn neighbors(&self, coords: &Coordinates) -> Vec<Coordinates> {
+        self.geometry().neighbors(coords)
+    }
+
+    /// Returns the flat indices of every neighbor of the cell at flat
+    /// `index`, in the same order as `neighbors`.
+    pub(crate) fn neighbor_indices(&self, index: usize) -> Vec<usize> {
+        self.geometry().neighbor_indices(index)
+    }
+
+    /// Returns everything that decides which cells are neighbors.
+    fn geometry(&self) -> Geometry<'_> {
+        Geometry {
+            dimensions: &self.dimensions,
+            max_changed_axes: self.adjacency.max_changed_axes(self.dimensions.len()),
+            wrap: self.wrap.as_deref(),
+        }
+    }
+
+    /// Returns the neighbors of `coords` that are still hidden (not revealed
+    /// and not flagged; question marks count as hidden).
+    ///
+    /// This is the question solvers and UIs ask most about a revealed number:
+    /// "where could its remaining mines be?"
+    pub fn hidden_neighbors(&self, coords: &Coordinates) -> Vec<Coordinates> {
+        self.neighbors_where(coords, CellState::is_hidden)
+    }
+
+    /// Returns the neighbors of `coords` that carry a flag.
+    pub fn flagged_neighbors(&self, coords: &Coordinates) -> Vec<Coordinates> {
+        self.neighbors_where(coords, |state| *state == CellState::Flagged)
+    }
+
+    /// Returns how many neighbors of `coords` carry a flag.
+    ///
+    /// This is the number to compare against a revealed number before
+    /// chording, and it is cheaper than `flagged_neighbors(coords).len()`.
+    pub fn adjacent_flags(&self, coords: &Coordinates) -> usize {
+        self.neighbor_indices(self.index_of(coords))
+            .into_iter()
+            .filter(|&n| self.states[n] == CellState::Flagged)
+            .count()
+    }
+
+    /// Counts the neighbors of `coords` by what the player can see of them,
+    /// in one pass. A solver display needs all of these numbers at once, and
+    /// asking for them one by one would walk the neighborhood several times.
+    pub fn neighbor_summary(&self, coords: &Coordinates) -> NeighborSummary {
+        let mut summary = NeighborSummary::default();
+        for n in self.neighbor_indices(self.index_of(coords)) {
+            match self.states[n] {
+                CellState::Flagged => summary.flagged += 1,
+                CellState::Revealed => {
+                    summary.revealed += 1;
+                    if self.mines.get(n) {
+                        summary.mines_if_known += 1;
+                    }
+                }
+                CellState::Hidden | CellState::Questioned => summary.hidden += 1,
+            }
+        }
+        summary
+    }
+
+    /// Returns the neighbors of `coords` whose cell state passes `keep`.
+    fn neighbors_where(
+        &self,
+        coords: &Coordinates,
+        keep: impl Fn(&CellState) -> bool,
+    ) -> Vec<Coordinates> {
+        self.neighbors(coords)
+            .into_iter()
+            .filter(|neighbor| keep(&self.states[self.index_of(neighbor)]))
+            .collect()
+    }
+
+    /// Computes the board's "3BV" (Bechtel's Board Benchmark Value).
+    ///
+    /// 3BV is the minimum number of clicks needed to clear the board without
+    /// flagging. Every connected region of zero cells takes one click (the
+    /// flood fill does the rest), and so does every numbered cell that no
+    /// flood fill will ever reach, i.e. one that doesn't touch a zero cell.
+    ///
+    /// The metric comes from the 2D Minesweeper community and is only
+    /// well-studied there. The computation below works in any number of
+    /// dimensions, but comparing values across dimensionalities is not
+    /// meaningful.
+    pub fn compute_3bv(&self) -> usize {
+        let is_zero = |index: usize| self.is_zero_at(index);
+
+        let mut visited = vec![false; self.cell_count()];
+        let mut clicks = 0;
+
+        // First, count the zero regions. Each unvisited zero cell starts a new
+        // region, which we walk with an explicit stack (recursion could
+        // overflow on very large boards). Numbered cells on the border of a
+        // region are marked as visited too, because the flood fill opens them.
+        for start in 0..self.cell_count() {
+            if visited[start] || !is_zero(start) {
+                continue;
+            }
+            clicks += 1;
+            visited[start] = true;
+            let mut stack = vec![start];
+            while let Some(index) = stack.pop() {
+                let coords = self.coords_of(index);
+                for neighbor in self.neighbors(&coords) {
+                    let neighbor_index = self.index_of(&neighbor);
+                    if visited[neighbor_index] || self.mines.get(neighbor_index) {
+                        continue;
+                    }
+                    visited[neighbor_index] = true;
+                    if is_zero(neighbor_index) {
+                        stack.push(neighbor_index);
+                    }
+                }
+            }
+        }
+
+        // Then, every safe cell that no region reached needs its own click.
+        clicks
+            + (0..self.cell_count())
+                .filter(|&i| !visited[i] && !self.mines.get(i))
+                .count()
+    }
+
+    /// Returns the set of mine positions as a bitset, indexed by flat index.
+    ///
+    /// The board already stores its mines this way, so this is just a copy;
+    /// callers that need to keep the layout while the board changes (or
+    /// that want to compare two layouts) can hold on to it.
+    pub fn mine_bitset(&self) -> Bitset {
+        self.mines.clone()
+    }
+
+    /// Calculates and sets the number of adjacent mines for each empty cell.
+    ///
+    /// This is O(cells * 3^N), which gets slow for large high-dimensional
+    /// boards. With the `parallel` feature enabled the work is spread over
+    /// all CPU cores; the result is identical either way.
+    fn calculate_adjacent_mines(&mut self) {
+        #[cfg(feature = "parallel")]
+        self.calculate_adjacent_mines_parallel();
+        #[cfg(not(feature = "parallel"))]
+        self.calculate_adjacent_mines_serial();
+    }
+
+    /// The single-threaded implementation of `calculate_adjacent_mines`.
+    // With `parallel` enabled, this is only kept around to cross-check the
+    // parallel version in tests.
+    #[cfg_attr(all(feature = "parallel", not(test)), allow(dead_code))]
+    fn calculate_adjacent_mines_serial(&mut self) {
+        let geometry = Geometry {
+            dimensions: &self.dimensions,
+            max_changed_axes: self.adjacency.max_changed_axes(self.dimensions.len()),
+            wrap: self.wrap.as_deref(),
+        };
+        for (i, count) in self.counts.iter_mut().enumerate() {
+            // We only need to calculate for empty cells
+            if !self.mines.get(i) {
+                *count = count_adjacent_mines(i, &geometry, &self.mines);
+            }
+        }
+    }
+
+    /// The multi-threaded implementation of `calculate_adjacent_mines`.
+    ///
+    /// Counting is embarrassingly parallel: once the mine positions are frozen
+    /// into a read-only bitset, every cell's count can be computed on its own,
+    /// and each thread only ever writes to the cells it was handed.
+    #[cfg(feature = "parallel")]
+    fn calculate_adjacent_mines_parallel(&mut self) {
+        use rayon::prelude::*;
+
+        let mines = &self.mines;
+        let geometry = Geometry {
+            dimensions: &self.dimensions,
+            max_changed_axes: self.adjacency.max_changed_axes(self.dimensions.len()),
+            wrap: self.wrap.as_deref(),
+        };
+        self.counts
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(i, count)| {
+                if !mines.get(i) {
+                    *count = count_adjacent_mines(i, &geometry, mines);
+                }
+            });
+    }
+
+    /// Places mines randomly on the board, choosing only among `candidates`.
+    fn place_mines(mines: &mut Bitset, candidates: &[usize], num_mines: usize, rng: &mut impl Rng) {
+        let chosen_indices = candidates.choose_multiple(rng, num_mines);
+
+        for &index in chosen_indices {
+            mines.set(index, true);
+        }
+    }
+
+    /// Moves every mine out of the `protected` cells onto random unprotected cells.
+    ///
+    /// This is the building block for first-click safety: the game calls it
+    /// *before* the first reveal is processed, so the player never sees the
+    /// original layout. The total number of mines never changes, only their
+    /// positions, and the adjacency counts are recomputed afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `protected` - The coordinates that must end up mine-free.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the protected cells are now mine-free.
+    /// * `false` if there are not enough free cells to receive the displaced
+    ///   mines. In that case the board is left completely untouched, so the
+    ///   caller can fall back to a smaller protected set.
+    pub fn relocate_mines(&mut self, protected: &[Coordinates]) -> bool {
+        self.relocate_mines_with_rng(protected, &mut rand::thread_rng())
+    }
+
+    /// Like `relocate_mines`, but picks the new mine positions with `rng`,
+    /// so that seeded games stay reproducible.
+    pub fn relocate_mines_with_rng(
+        &mut self,
+        protected: &[Coordinates],
+        rng: &mut impl Rng,
+    ) -> bool {
+        // A boolean mask makes the "is this cell protected?" question O(1),
+        // and also takes care of duplicate coordinates in `protected`.
+        let mut is_protected = vec![false; self.cell_count()];
+        for coords in protected {
+            is_protected[self.index_of(coords)] = true;
+        }
+
+        let displaced: Vec<usize> = (0..self.cell_count())
+            .filter(|&i| is_protected[i] && self.mines.get(i))
+            .collect();
+        if displaced.is_empty() {
+            return true;
+        }
+
+        // Candidate destinations are cells that are neither protected nor
+        // already holding a mine.
+        let free: Vec<usize> = (0..self.cell_count())
+            .filter(|&i| !is_protected[i] && !self.mines.get(i))
+            .collect();
+        if free.len() < displaced.len() {
+            return false;
+        }
+
+        let destinations: Vec<usize> = free
+            .choose_multiple(rng, displaced.len())
+            .copied()
+            .collect();
+
+        for index in displaced {
+            self.mines.set(index, false);
+        }
+        for index in destinations {
+            self.mines.set(index, true);
+        }
+
+        // Moving mines changes the numbers around both the old and the new
+        // positions, so the simplest correct thing is a full recount.
+        self.calculate_adjacent_mines();
+        true
+    }
+
+    /// Places or removes a single mine, keeping the adjacency counts correct.
+    ///
+    /// This is meant for board editors. Only the changed cell's neighborhood
+    /// is updated (each neighboring number goes up or down by one), so
+    /// editing stays fast even on huge boards. The cell's reveal state is
+    /// left alone. A number that has saturated (see `MINE_SENTINEL`) is
+    /// recounted instead, since it no longer says how many mines there are.
+    ///
+    /// # Arguments
+    ///
+    /// * `coords` - The cell to change.
+    /// * `is_mine` - Whether the cell should hold a mine afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Any error from `validate_coords`, in which case nothing changes.
+    pub fn set_mine(&mut self, coords: &Coordinates, is_mine: bool) -> Result<(), BoardError> {
+        self.validate_coords(coords)?;
+        let index = self.index_of(coords);
+        if self.mines.get(index) == is_mine {
+            return Ok(());
+        }
+
+        let neighbors = self.neighbor_indices(index);
+        let mut saturated = Vec::new();
+        for &neighbor_index in &neighbors {
+            if self.mines.get(neighbor_index) {
+                continue;
+            }
+            let adjacent_mines = &mut self.counts[neighbor_index];
+            if *adjacent_mines == MINE_SENTINEL - 1 {
+                // One more would be the sentinel, and one less may still
+                // be too many to store.
+                saturated.push(neighbor_index);
+            } else if is_mine {
+                *adjacent_mines += 1;
+            } else {
+                *adjacent_mines -= 1;
+            }
+        }
+
+        self.mines.set(index, is_mine);
+        if is_mine {
+            self.num_mines += 1;
+        } else {
+            self.num_mines -= 1;
+            // A cell that stops being a mine needs its own count, which
+            // mines never carry.
+            self.counts[index] =
+                saturating_count(neighbors.iter().filter(|&&n| self.mines.get(n)).count());
+        }
+        for index in saturated {
+            let mines = self
+                .neighbor_indices(index)
+                .into_iter()
+                .filter(|&n| self.mines.get(n))
+                .count();
+            self.counts[index] = saturating_count(mines);
+        }
+        Ok(())
+    }
+
+    /// Recomputes the adjacency counts of the cells within `radius` steps of
+    /// `coords` along every axis, leaving the rest of the board alone.
+    ///
+    /// `set_mine` already keeps the counts right, but code that changes the
+    /// mine layout in bulk (say, stamping a pattern of mines into one corner)
+    /// would otherwise need a full recompute, which is slow on large
+    /// high-dimensional boards. A mine only affects its own neighbors, so
+    /// after changing mines within `r` steps of a cell, a radius of `r + 1`
+    /// around it covers every count that can have changed. On wrapping axes
+    /// the region wraps around the edge like the neighborhoods do.
+    ///
+    /// `num_mines` is left alone too; keep it in sync yourself (see
+    /// `verify_integrity`).
+    ///
+    /// # Errors
+    ///
+    /// Any error from `validate_coords`, in which case nothing changes.
+    pub fn recalculate_region(
+        &mut self,
+        coords: &Coordinates,
+        radius: usize,
+    ) -> Result<(), BoardError> {
+        self.validate_coords(coords)?;
+        // A radius as long as the longest axis already reaches every cell
+        // along it, so clamping keeps huge radii from blowing up the loop.
+        let longest = self.dimensions.iter().copied().max().unwrap_or(0);
+        let reach = radius.min(longest) as i32;
+        let wrap = self.wrap.as_deref().unwrap_or(&[]);
+        let corner = vec![2 * reach as usize; coords.len()];
+        let mut region: Vec<usize> = range_iter(&vec![0; coords.len()], &corner)
+            .filter_map(|step| {
+                let offset: Vec<i32> = step.iter().map(|&s| s as i32 - reach).collect();
+                apply_offset_wrapping(coords, &offset, &self.dimensions, wrap)
+            })
+            .map(|cell| self.index_of(&cell))
+            .collect();
+        // On a short wrapping axis the region can overlap itself.
+        region.sort_unstable();
+        region.dedup();
+
+        let geometry = self.geometry();
+        let counts: Vec<(usize, u16)> = region
+            .into_iter()
+            .filter(|&index| !self.mines.get(index))
+            .map(|index| {
+                let mines = geometry
+                    .neighbor_indices(index)
+                    .into_iter()
+                    .filter(|&neighbor| self.mines.get(neighbor))
+                    .count();
+                (index, saturating_count(mines))
+            })
+            .collect();
+        for (index, adjacent_mines) in counts {
+            self.counts[index] = adjacent_mines;
+        }
+        Ok(())
+    }
+
+    /// Lists the cells whose state differs between this board and `other`,
+    /// with their state on `other`, in flat index order.
+    ///
+    /// This is the delta that turns this board's view into `other`'s, so a
+    /// networked game only has to send the cells that changed, and an undo
+    /// stack only has to keep the changes of each move. Only cell states are
+    /// compared; the mines are assumed to be the same (see `same_layout`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the boards have different dimensions.
+    pub fn diff(&self, other: &Board) -> Vec<(Coordinates, CellState)> {
+        assert_eq!(
+            self.dimensions, other.dimensions,
+            "only boards of the same shape can be compared"
+        );
+        self.states
+            .iter()
+            .zip(&other.states)
+            .enumerate()
+            .filter(|(_, (mine, theirs))| mine != theirs)
+            .map(|(index, (_, theirs))| (self.coords_of(index), theirs.clone()))
+            .collect()
+    }
+
+    /// Hides every cell again and removes all flags and question marks,
+    /// keeping the mines and their counts.
+    ///
+    /// This strips the player's progress, e.g. before sharing a puzzle, so
+    /// afterwards the board is in the same state as when it was generated:
+    /// the reveal numbers reported by `revealed_at_move` start over, too.
+    /// Settings such as the reveal limit or the adjacency stay as they are.
+    pub fn reset_states(&mut self) {
+        self.states.fill(CellState::Hidden);
+        self.revealed_at.fill(None);
+        self.reveal_moves = 0;
+    }
+
+    /// Toggles a flag on a cell.
+    ///
+    /// Like every method on `Board`, this does not check whether the game is
+    /// over: the board only knows about cells, not about rules. Front-ends
+    /// should go through `Game::toggle_flag`, which refuses to act once the
+    /// game has been won or lost (see `Game::can_modify`).
+    ///
+    /// # Arguments
+    ///
+    /// * `coords` - The coordinates of the cell to toggle the flag on.
+    ///
+    /// # Returns
+    ///
+    /// The cell's state after the toggle (unchanged for a revealed or frozen
+    /// cell), so a UI can redraw it without asking again, or `None` if the
+    /// coordinates are not on the board.
+    pub fn toggle_flag(&mut self, coords: &crate::coordinates::Coordinates) -> Option<CellState> {
+        // Coordinates beyond the edge are a normal "no such cell", but the
+        // wrong number of components is a bug in the caller.
+        debug_assert_eq!(coords.len(), self.dimensions.len());
+        let index = self.checked_index(coords)?;
+        let frozen = self.frozen.get(index);
+        let state = &mut self.states[index];
+        match state {
+            _ if frozen => (),
+            CellState::Hidden | CellState::Questioned => *state = CellState::Flagged,
+            CellState::Flagged => *state = CellState::Hidden,
+            CellState::Revealed => (),
+        }
+        Some(state.clone())
+    }
+
+    /// Moves the cell at `coords` to the next mark in the board's
+    /// `MarkCycle` (by default Hidden → Flagged → Questioned → Hidden).
+    ///
+    /// Revealed and frozen cells are left alone. Like `toggle_flag`, this
+    /// doesn't know about game rules; use `Game::cycle_mark` during play.
+    pub fn cycle_mark(&mut self, coords: &Coordinates) {
+        debug_assert_eq!(self.validate_coords(coords), Ok(()));
+        let index = self.checked_index(coords);
+        if let Some(index) = index.filter(|&index| !self.frozen.get(index)) {
+            self.states[index] = self.mark_cycle.next(&self.states[index]);
+        }
+    }
+
+    /// Reveals every cell on the board, mines and flagged cells included.
+    ///
+    /// This is a debugging and spoiler aid: afterwards the whole layout can
+    /// be inspected, e.g. with `render_2d`. All the cells it opens share one
+    /// reveal number (see `revealed_at_move`). It knows nothing about game
+    /// rules; `Game::reveal_all_debug` is the version that keeps a game from
+    /// counting the result as a win.
+    pub fn reveal_all(&mut self) {
+        let stamp = self.reveal_moves + 1;
+        let mut any = false;
+        for (state, revealed_at) in self.states.iter_mut().zip(&mut self.revealed_at) {
+            if *state != CellState::Revealed {
+                *state = CellState::Revealed;
+                *revealed_at = Some(stamp);
+                any = true;
+            }
+        }
+        if any {
+            self.reveal_moves = stamp;
+        }
+    }
+
+    /// Reveals every hidden cell that doesn't carry a flag.
+    ///
+    /// This is the "I've flagged all the mines, open the rest" shortcut. It is
+    /// high-stakes: if any flag is wrong, a mine is among the cells revealed.
+    /// Every unflagged cell is revealed either way, so the player can see
+    /// exactly where their flags went wrong. Frozen cells stay hidden.
+    ///
+    /// # Returns
+    ///
+    /// * `RevealOutcome::HitMine` with the first mine found (in index order),
+    ///   if any unflagged cell was a mine.
+    /// * `RevealOutcome::NoOp` if there was no unflagged hidden cell left.
+    /// * `RevealOutcome::Safe` with every newly revealed cell otherwise.
+    pub fn reveal_all_unflagged(&mut self) -> RevealOutcome {
+        let mut revealed = Vec::new();
+        let mut first_mine = None;
+        let stamp = self.reveal_moves + 1;
+
+        // No flood fill is needed here: we are revealing every eligible cell
+        // anyway, so a simple pass over the flat vector does the job.
+        for index in 0..self.cell_count() {
+            if !self.states[index].is_hidden() || self.frozen.get(index) {
+                continue;
+            }
+            self.states[index] = CellState::Revealed;
+            self.revealed_at[index] = Some(stamp);
+            let coords = self.coords_of(index);
+            if self.mines.get(index) && first_mine.is_none() {
+                first_mine = Some(coords.clone());
+            }
+            revealed.push(coords);
+        }
+        if revealed.is_empty() {
+            return RevealOutcome::NoOp;
+        }
+        self.reveal_moves = stamp;
+
+        match first_mine {
+            Some(coords) => RevealOutcome::HitMine(coords),
+            None => RevealOutcome::Safe {
+                revealed,
+                adjacent_mines: None,
+            },
+        }
+    }
+
+    /// Reveals several cells, one after the other, as if each had been
+    /// clicked in turn.
+    ///
+    /// This is meant for scripted play and AI players, which often decide on
+    /// a batch of moves at once. Processing stops at the first mine: the
+    /// remaining coordinates are left untouched, just as a real player
+    /// couldn't keep clicking after losing.
+    ///
+    /// # Returns
+    ///
+    /// * `RevealOutcome::HitMine` with the coordinates that hit a mine.
+    /// * `RevealOutcome::NoOp` if none of the cells could be revealed
+    ///   (they were all revealed or flagged already).
+    /// * `RevealOutcome::Safe` with every newly revealed cell otherwise,
+    ///   including those revealed by flood fills, in reveal order.
+    pub fn reveal_many(&mut self, coords: &[Coordinates]) -> RevealOutcome {
+        debug_assert!(coords.iter().all(|c| self.validate_coords(c).is_ok()));
+        let limit = self.reveal_limit.unwrap_or(usize::MAX);
+        let mut revealed = Vec::new();
+
+        for target in coords {
+            let index = self.index_of(target);
+            let flood = self.flood_reveal(index, limit);
+            // As in `try_reveal`, only the clicked cell can be a mine.
+            if !flood.revealed.is_empty() && self.mines.get(index) {
+                return RevealOutcome::HitMine(target.clone());
+            }
+            revealed.extend(flood.revealed.into_iter().map(|i| self.coords_of(i)));
+        }
+
+        if revealed.is_empty() {
+            return RevealOutcome::NoOp;
+        }
+        // A single click that opened just one cell, which isn't a zero (a
+        // zero would have cascaded, unless everything around it was open).
+        let adjacent_mines = match (coords, &revealed[..]) {
+            ([target], [only]) if target == only => match self.kind_at(self.index_of(only)) {
+                CellKind::Empty { adjacent_mines } if adjacent_mines > 0 => Some(adjacent_mines),
+                _ => None,
+            },
+            _ => None,
+        };
+        RevealOutcome::Safe {
+            revealed,
+            adjacent_mines,
+        }
+    }
+
+    /// "Chords" on a revealed number: if it touches exactly as many flags as
+    /// its number says, every other hidden neighbor is revealed at once.
+    ///
+    /// This is the classic two-button shortcut for clearing around a number
+    /// whose mines are all flagged. The flags are trusted: if one of them is
+    /// wrong, a mine is among the cells revealed. Only the neighbors of the
+    /// board's `Adjacency` are counted and revealed.
+    ///
+    /// # Returns
+    ///
+    /// * `RevealOutcome::NoOp` (and nothing changes) unless `coords` is a
+    ///   revealed number with the right number of flags around it.
+    /// * Otherwise, what `reveal_many` returns for the hidden neighbors.
+    pub fn chord(&mut self, coords: &Coordinates) -> RevealOutcome {
+        debug_assert!(self.validate_coords(coords).is_ok());
+        let index = self.index_of(coords);
+        let CellKind::Empty { adjacent_mines } = self.kind_at(index) else {
+            return RevealOutcome::NoOp;
+        };
+        if self.states[index] != CellState::Revealed
+            || self.adjacent_flags(coords) != adjacent_mines as usize
+        {
+            return RevealOutcome::NoOp;
+        }
+        let targets = self.hidden_neighbors(coords);
+        self.reveal_many(&targets)
+    }
+
+    /// Reveals a cell.
+    ///
+    /// This does not check whether the game is over; use `Game::reveal` to get
+    /// the rules of play enforced.
+    ///
+    /// # Arguments
+    ///
+    /// * `coords` - The coordinates of the cell to reveal.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a mine was revealed, `false` otherwise.
+    pub fn reveal(&mut self, coords: &crate::coordinates::Coordinates) -> bool {
+        // Invalid coordinates are a bug in the caller; `try_reveal` reports
+        // them as an error, but `reveal` has no way to, so debug builds stop
+        // right here instead of quietly answering `false`.
+        debug_assert_eq!(self.validate_coords(coords), Ok(()));
+        // Only a cascade from a zero cell can hit the reveal limit, and such
+        // a cascade never involves a mine, so on error the answer is `false`.
+        self.try_reveal(coords).unwrap_or(false)
+    }
+
+    /// Reveals a cell, reporting whether the board's reveal limit cut the
+    /// flood fill short (see `set_reveal_limit`).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` if a mine was revealed, `Ok(false)` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// * `BoardError::RevealLimitExceeded` if the flood fill stopped at the
+    ///   limit. The cells revealed up to that point stay revealed.
+    /// * Any error from `validate_coords`, in which case nothing is revealed.
+    pub fn try_reveal(&mut self, coords: &Coordinates) -> Result<bool, BoardError> {
+        self.validate_coords(coords)?;
+        let index = self.index_of(coords);
+        let limit = self.reveal_limit.unwrap_or(usize::MAX);
+        let flood = self.flood_reveal(index, limit);
+        if flood.truncated {
+            return Err(BoardError::RevealLimitExceeded { limit });
+        }
+
+        // The clicked cell is always the first one revealed, and a cascade
+        // never continues from a mine, so only the clicked cell can be one.
+        Ok(!flood.revealed.is_empty() && self.mines.get(index))
+    }
+
+    /// Reveals a cell, but stops the flood fill after `max_cells` cells.
+    ///
+    /// This simulates a limited "scan" for timed challenge modes. The flood
+    /// fill is breadth-first, so the revealed cells always form a contiguous
+    /// patch centered on the click, and the rest of the region stays hidden.
+    ///
+    /// # Arguments
+    ///
+    /// * `coords` - The coordinates of the cell to reveal.
+    /// * `max_cells` - The maximum number of cells to reveal.
+    ///
+    /// # Returns
+    ///
+    /// How many cells were actually revealed. The board's own reveal limit
+    /// still applies, if it is smaller than `max_cells`.
+    ///
+    /// # Errors
+    ///
+    /// Any error from `validate_coords`, in which case nothing is revealed.
+    pub fn reveal_limited(
+        &mut self,
+        coords: &Coordinates,
+        max_cells: usize,
+    ) -> Result<usize, BoardError> {
+        self.validate_coords(coords)?;
+        let index = self.index_of(coords);
+        let limit = max_cells.min(self.reveal_limit.unwrap_or(usize::MAX));
+        Ok(self.flood_reveal(index, limit).revealed.len())
+    }
+
+    /// Returns how many cells revealing `coords` would uncover, without
+    /// changing the board.
+    ///
+    /// That is `0` for a flagged or already revealed cell, `1` for a number
+    /// or a mine, and the whole cascade for a zero cell, e.g. so that a UI
+    /// can size its animation before committing to the reveal. The board's
+    /// reveal limit, adjacency and flood policy are taken into account, so
+    /// the answer always matches what `reveal` would do.
+    ///
+    /// # Errors
+    ///
+    /// Any error from `validate_coords`.
+    pub fn preview_reveal(&self, coords: &Coordinates) -> Result<usize, BoardError> {
+        self.validate_coords(coords)?;
+        let index = self.index_of(coords);
+        let limit = self.reveal_limit.unwrap_or(usize::MAX);
+        Ok(self.plan_flood(index, limit).revealed.len())
+    }
+
+    /// Reveals a cell and its cascade one cell at a time, as an iterator.
+    ///
+    /// Each call to `next` reveals one more cell, in the same breadth-first
+    /// order as `reveal`, and returns its coordinates, so a UI can animate
+    /// the cascade and yield to its event loop between steps. Dropping the
+    /// iterator early cancels the rest of the cascade: the cells not yet
+    /// returned stay hidden. Nothing is revealed before the first `next`.
+    ///
+    /// The flood fill itself advances one step per `next` too: a cell's
+    /// neighbors are only looked at once the cell is revealed, so stopping
+    /// after a few steps of a huge cascade costs only those few steps.
+    ///
+    /// Like `reveal`, this doesn't check whether the game is over, and the
+    /// clicked cell is returned even if it is a mine.
+    ///
+    /// # Errors
+    ///
+    /// Any error from `validate_coords`, in which case nothing is revealed.
+    pub fn reveal_stepwise(
+        &mut self,
+        coords: &Coordinates,
+    ) -> Result<RevealIterator<'_>, BoardError> {
+        self.validate_coords(coords)?;
+        let start = self.index_of(coords);
+        let budget = self.reveal_limit.unwrap_or(usize::MAX);
+        let mut steps = RevealIterator {
+            included: Bitset::new(self.cell_count()),
+            pending: VecDeque::new(),
+            budget,
+            stamp: None,
+            board: self,
+        };
+        // The same checks `plan_flood` starts with.
+        if steps.board.states[start].is_hidden() && !steps.board.frozen.get(start) {
+            steps.include(start);
+        }
+        Ok(steps)
+    }
+
+    /// The flood fill shared by all the reveal methods.
+    ///
+    /// Reveals the cells `plan_flood` picks, and stamps them with a new
+    /// reveal number (see `revealed_at_move`) if there are any.
+    fn flood_reveal(&mut self, start: usize, max_cells: usize) -> Flood {
+        let flood = self.plan_flood(start, max_cells);
+        if flood.revealed.is_empty() {
+            return flood;
+        }
+
+        self.reveal_moves += 1;
+        for &index in &flood.revealed {
+            self.states[index] = CellState::Revealed;
+            self.revealed_at[index] = Some(self.reveal_moves);
+        }
+        flood
+    }
+
+    /// Works out which cells a reveal of `start` uncovers, without changing
+    /// anything.
+    ///
+    /// The cell at `start` is included and, if it has no adjacent mines, the
+    /// fill keeps spreading outwards until the region is bordered by
+    /// numbered cells. Already-revealed cells are never included, and
+    /// neither are flagged ones unless the board's `FloodPolicy` says to
+    /// ignore flags (a flagged `start` is left alone either way), nor frozen
+    /// ones (see `set_frozen`). With
+    /// cascading turned off (see `set_cascade`), only `start` is included.
+    ///
+    /// The fill is breadth-first and uses an explicit queue rather than
+    /// recursion: on a large high-dimensional board a recursive fill can go
+    /// deep enough to overflow the stack, and breadth-first order is what
+    /// makes a truncated fill (see `reveal_limited`) look natural.
+    ///
+    /// # Returns
+    ///
+    /// A `Flood` listing the cells to reveal and whether `max_cells` cut the
+    /// fill short.
+    fn plan_flood(&self, start: usize, max_cells: usize) -> Flood {
+        let mut revealed = Vec::new();
+
+        // Can't reveal a flagged, frozen or already revealed cell
+        if !self.states[start].is_hidden() || self.frozen.get(start) {
+            return Flood {
+                revealed,
+                truncated: false,
+            };
+        }
+        if max_cells == 0 {
+            return Flood {
+                revealed,
+                truncated: true,
+            };
+        }
+
+        // One bit per cell: checking and marking a cell is a shift and a
+        // mask, with none of the hashing a set would cost on every step of
+        // a large cascade.
+        let mut included = Bitset::new(self.cell_count());
+        included.set(start, true);
+        let mut queue = VecDeque::from([start]);
+        revealed.push(start);
+
+        while let Some(index) = queue.pop_front() {
+            if !self.spreads_from(index) {
+                continue;
+            }
+
+            for neighbor_index in self.neighbor_indices(index) {
+                if !self.floods_into(neighbor_index) || included.get(neighbor_index) {
+                    continue;
+                }
+                if revealed.len() == max_cells {
+                    return Flood {
+                        revealed,
+                        truncated: true,
+                    };
+                }
+                // Including the cell *when it is queued* (rather than when
+                // it is processed) guarantees it is queued once.
+                included.set(neighbor_index, true);
+                revealed.push(neighbor_index);
+                queue.push_back(neighbor_index);
+            }
+        }
+
+        Flood {
+            revealed,
+            truncated: false,
+        }
+    }
+
+    /// Returns whether revealing the cell at `index` carries a flood fill on
+    /// to its neighbors: only empty cells with no adjacent mines spread the
+    /// reveal, and only while cascading is on.
+    fn spreads_from(&self, index: usize) -> bool {
+        self.cascade && self.is_zero_at(index)
+    }
+
+    /// Returns whether a flood fill that reaches the cell at `index` opens
+    /// it: hidden cells yes, flagged ones only if the `FloodPolicy` ignores
+    /// flags, frozen ones never.
+    fn floods_into(&self, index: usize) -> bool {
+        let state = &self.states[index];
+        (state.is_hidden()
+            || (*state == CellState::Flagged && self.flood_policy == FloodPolicy::IgnoreFlags))
+            && !self.frozen.get(index)
+    }
+}
+
+/// The result of `Board::plan_flood` and `Board::flood_reveal`.
+struct Flood {
+    /// The flat indices of every cell revealed, in the order they were
+    /// revealed. The first one, if any, is always the starting cell.
+    revealed: Vec<usize>,
+
+    /// Whether the fill stopped early because it ran out of its cell budget,
+    /// leaving part of the region hidden.
+    truncated: bool,
+}
+
+/// Returns the number of cells a board with these dimensions has.
+///
+/// # Errors
+///
+/// Returns `BoardError::TooLarge` if the count exceeds `max_cells`, including
+/// when it is too large to even fit in a `usize`. A plain `product()` would
+/// silently wrap around in that case (in release builds), and the board would
+/// be allocated with the wrong number of cells.
+pub(crate) fn checked_cell_count(
+    dimensions: &[usize],
+    max_cells: usize,
+) -> Result<usize, BoardError> {
+    dimensions
+        .iter()
+        .try_fold(1_usize, |acc, &size| acc.checked_mul(size))
+        .filter(|&count| count <= max_cells)
+        .ok_or(BoardError::TooLarge { max_cells })
+}
+
+/// Checks that `coords` name a cell of a board with these dimensions. See
+/// `Board::validate_coords`, which is this for an existing board; this
+/// version also works for constructors, before there is a board to ask.
+fn check_coords(coords: &Coordinates, dimensions: &[usize]) -> Result<(), BoardError> {
+    if coords.len() != dimensions.len() {
+        return Err(BoardError::WrongCoordinateCount {
+            expected: dimensions.len(),
+            actual: coords.len(),
+        });
+    }
+    if coords.iter().zip(dimensions).any(|(&c, &d)| c >= d) {
+        return Err(BoardError::OutOfBounds {
+            coords: coords.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Reveals a cascade one cell per step. See `Board::reveal_stepwise`.
+///
+/// This is `Board::plan_flood` taken apart: the queue and the set of cells
+/// already included live here between steps, and each step reveals the
+/// next cell in the queue and only then queues its neighbors. Cells come
+/// out in the order they were queued, which is exactly the order
+/// `plan_flood` lists them in.
+pub struct RevealIterator<'a> {
+    board: &'a mut Board,
+    // The cells queued so far (revealed or still pending).
+    included: Bitset,
+    // The flat indices queued but not revealed yet, in reveal order.
+    pending: VecDeque<usize>,
+    // How many more cells the board's reveal limit lets the fill queue.
+    budget: usize,
+    // The reveal number (see `Board::revealed_at_move`) of this cascade,
+    // assigned when its first cell is revealed.
+    stamp: Option<usize>,
+}
+
+impl RevealIterator<'_> {
+    /// Queues the cell at `index`, if the board's reveal limit allows it.
+    ///
+    /// Once the budget is spent, nothing is queued any more, just as
+    /// `plan_flood` stops at the first cell it has no budget left for. The
+    /// cells already queued are still revealed.
+    fn include(&mut self, index: usize) {
+        if self.budget > 0 {
+            self.budget -= 1;
+            self.included.set(index, true);
+            self.pending.push_back(index);
+        }
+    }
+}
+
+impl Iterator for RevealIterator<'_> {
+    type Item = Coordinates;
+
+    fn next(&mut self) -> Option<Coordinates> {
+        let index = self.pending.pop_front()?;
+        let board = &mut *self.board;
+        let stamp = *self.stamp.get_or_insert_with(|| {
+            board.reveal_moves += 1;
+            board.reveal_moves
+        });
+        board.states[index] = CellState::Revealed;
+        board.revealed_at[index] = Some(stamp);
+        let coords = board.coords_of(index);
+
+        if self.board.spreads_from(index) {
+            for neighbor in self.board.neighbor_indices(index) {
+                if self.board.floods_into(neighbor) && !self.included.get(neighbor) {
+                    self.include(neighbor);
+                }
+            }
+        }
+        Some(coords)
+    }
+}
+
+/// Everything that decides which cells of a board are neighbors: its shape,
+/// its `Adjacency`, and which axes wrap around.
+///
+/// This only borrows the board's settings, not its cells, so it can be used
+/// while the cells are being mutated, e.g. by the parallel adjacency count.
+#[derive(Clone, Copy)]
+struct Geometry<'a> {
+    dimensions: &'a [usize],
+    max_changed_axes: usize,
+    wrap: Option<&'a [bool]>,
+}
+
+impl Geometry<'_> {
+    /// See `Board::neighbors`.
+    fn neighbors(&self, coords: &Coordinates) -> Vec<Coordinates> {
+        match self.wrap {
+            None => get_neighbors_by_order(coords, self.dimensions, self.max_changed_axes),
+            Some(wrap) => {
+                get_neighbors_wrapping(coords, self.dimensions, wrap, self.max_changed_axes)
+            }
+        }
+    }
+
+    /// See `Board::neighbor_indices`.
+    fn neighbor_indices(&self, index: usize) -> Vec<usize> {
+        match self.wrap {
+            // Without wrapping, the neighbors can be found without building
+            // any coordinates.
+            None => get_neighbor_indices_by_order(index, self.dimensions, self.max_changed_axes),
+            Some(_) => self
+                .neighbors(&to_coords(index, self.dimensions))
+                .iter()
+                .map(|neighbor| to_index(neighbor, self.dimensions))
+                .collect(),
+        }
+    }
+}
+
+/// Counts the mines around the cell at flat `index`.
+///
+/// This is a free function rather than a method so that it only borrows what
+/// it reads, which lets the parallel path call it while `counts` is mutably
+/// borrowed by the thread pool.
+fn count_adjacent_mines(index: usize, geometry: &Geometry, mines: &Bitset) -> u16 {
+    let count = geometry
+        .neighbor_indices(index)
+        .into_iter()
+        .filter(|&neighbor| mines.get(neighbor))
+        .count();
+    saturating_count(count)
+}
+
+/// Converts a number of adjacent mines to the stored `u16`.
+///
+/// Counts that don't fit stop one below `MINE_SENTINEL` instead of wrapping
+/// around, so they can't pass for a small number or for a mine. That takes
+/// more than 65,000 neighboring mines, i.e. at least 11 dimensions.
+fn saturating_count(count: usize) -> u16 {
+    count.min(usize::from(MINE_SENTINEL - 1)) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::{Cell, CellKind, CellState};
+
+    #[test]
+    fn test_calculate_adjacent_mines_2d() {
+        let dimensions = vec![3, 3];
+        let total_cells = 9;
+        let mut mines = Bitset::new(total_cells);
+
+        // Place mines at (0,0) [index 0] and (2,2) [index 8]
+        mines.set(0, true);
+        mines.set(8, true);
+
+        let mut board = Board {
+            strides: vec![1, 3],
+            dimensions,
+            states: vec![CellState::Hidden; total_cells],
+            mines,
+            counts: vec![0; total_cells],
+            num_mines: 2,
+            reveal_limit: None,
+            mark_cycle: MarkCycle::default(),
+            adjacency: Adjacency::default(),
+            flood_policy: FloodPolicy::default(),
+            cascade: true,
+            frozen: Bitset::new(total_cells),
+            wrap: None,
+            axis_labels: None,
+            revealed_at: vec![None; total_cells],
+            reveal_moves: 0,
+        };
+
+        board.calculate_adjacent_mines();
+
+        // Check adjacent mine counts for a few cells
+        // Cell (1,0) [index 1] should have 1 neighbor mine.
+        if let CellKind::Empty { adjacent_mines } = board.cell_at(1).kind {
+            assert_eq!(adjacent_mines, 1);
+        } else {
+            panic!("Cell (1,0) should be empty");
+        }
+
+        // Cell (0,1) [index 3] should have 1 neighbor mine.
+        if let CellKind::Empty { adjacent_mines } = board.cell_at(3).kind {
+            assert_eq!(adjacent_mines, 1);
+        } else {
+            panic!("Cell (0,1) should be empty");
+        }
+
+        // Cell (1,1) [index 4] should have 2 neighbor mines.
+        if let CellKind::Empty { adjacent_mines } = board.cell_at(4).kind {
+            assert_eq!(adjacent_mines, 2);
+        } else {
+            panic!("Cell (1,1) should be empty");
+        }
+
+        // Ensure mine cells are untouched
+        assert_eq!(board.cell_at(0).kind, CellKind::Mine);
+        assert_eq!(board.cell_at(8).kind, CellKind::Mine);
+    }
+
+    #[test]
+    fn test_relocate_mines_clears_protected_cells() {
+        let mut board = Board::new(vec![3, 3], 0).unwrap();
+        board.mines.set(0, true);
+        board.mines.set(1, true);
+        board.calculate_adjacent_mines();
+
+        let protected = vec![vec![0, 0], vec![1, 0]];
+        assert!(board.relocate_mines(&protected));
+
+        // The protected cells are now empty, but the mine count is unchanged.
+        assert_ne!(board.cell_at(0).kind, CellKind::Mine);
+        assert_ne!(board.cell_at(1).kind, CellKind::Mine);
+        let mines = board.cells().filter(|c| c.kind == CellKind::Mine).count();
+        assert_eq!(mines, 2);
+    }
+
+    #[test]
+    fn test_relocate_mines_without_room_leaves_board_untouched() {
+        let mut board = Board::new(vec![2, 2], 3).unwrap();
+        let everything: Vec<Coordinates> =
+            (0..4).map(|i| to_coords(i, &board.dimensions)).collect();
+        let before: Vec<CellKind> = board.cells().map(|c| c.kind.clone()).collect();
+
+        assert!(!board.relocate_mines(&everything));
+
+        let after: Vec<CellKind> = board.cells().map(|c| c.kind.clone()).collect();
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_from_mines_places_exact_layout() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0], vec![2, 2], vec![0, 0]]);
+
+        // The duplicate coordinate only counts once.
+        assert_eq!(board.num_mines(), 2);
+        assert_eq!(board.cell_at(0).kind, CellKind::Mine);
+        assert_eq!(board.cell_at(8).kind, CellKind::Mine);
+        assert_eq!(board.cell_at(4).kind, CellKind::Empty { adjacent_mines: 2 });
+    }
+
+    #[test]
+    fn test_compute_3bv_hand_built() {
+        // A 7x1 strip: `1 * 2 * 1 0 0`.
+        // The zero region {5, 6} is one click (and also opens cell 4), while
+        // cells 0 and 2 touch no zero cell and need a click each.
+        let board = Board::from_mines(vec![7, 1], &[vec![1, 0], vec![3, 0]]);
+        assert_eq!(board.compute_3bv(), 3);
+
+        // A single corner mine on a 3x3 board: one click opens everything.
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        assert_eq!(board.compute_3bv(), 1);
+    }
+
+    #[test]
+    fn test_new_with_min_3bv() {
+        let board = Board::new_with_min_3bv(vec![8, 8], 10, 20, 42).unwrap();
+        assert!(board.compute_3bv() >= 20);
+        assert_eq!(board.num_mines(), 10);
+
+        // The same seed reproduces the same board.
+        let again = Board::new_with_min_3bv(vec![8, 8], 10, 20, 42).unwrap();
+        let kinds = |b: &Board| b.cells().map(|c| c.kind.clone()).collect::<Vec<_>>();
+        assert_eq!(kinds(&board), kinds(&again));
+
+        // An impossible requirement gives up instead of looping forever.
+        assert_eq!(
+            Board::new_with_min_3bv(vec![2, 2], 1, 100, 42).err(),
+            Some(BoardError::GenerationFailed {
+                attempts: MAX_GENERATION_ATTEMPTS
+            })
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_and_serial_counts_match() {
+        let mut board = Board::new_with_min_3bv(vec![5, 4, 3, 4], 40, 0, 7).unwrap();
+        board.calculate_adjacent_mines_serial();
+        let serial: Vec<CellKind> = board.cells().map(|c| c.kind.clone()).collect();
+
+        board.calculate_adjacent_mines_parallel();
+        let parallel: Vec<CellKind> = board.cells().map(|c| c.kind.clone()).collect();
+
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_storage_matches_the_per_cell_representation() {
+        // The classic 3x3 fixture (mines at (0,0) and (2,2)) as the board
+        // used to store it: one `Cell` per square, counts written out.
+        let empty = |adjacent_mines| CellKind::Empty { adjacent_mines };
+        let mut expected: Vec<Cell> = [
+            CellKind::Mine,
+            empty(1),
+            empty(0),
+            empty(1),
+            empty(2),
+            empty(1),
+            empty(0),
+            empty(1),
+            CellKind::Mine,
+        ]
+        .into_iter()
+        .map(|kind| Cell {
+            state: CellState::Hidden,
+            kind,
+        })
+        .collect();
+        let same_cells = |board: &Board, expected: &[Cell]| {
+            assert_eq!(board.cell_count(), expected.len());
+            for (cell, want) in board.cells().zip(expected) {
+                assert_eq!(cell.kind, want.kind);
+                assert_eq!(cell.state, want.state);
+            }
+        };
+
+        // Built from coordinates or from a bitset, the board reads back the
+        // same cells.
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0], vec![2, 2]]);
+        let from_bits = Board::from_mine_bitset(vec![3, 3], &board.mine_bitset());
+        same_cells(&board, &expected);
+        same_cells(&from_bits, &expected);
+        assert_eq!(board.num_mines(), 2);
+        assert_eq!(board.kind_counts(), (2, 7));
+        assert_eq!(board.safe_cells(), 7);
+
+        // A number opens alone, a zero floods up to the numbers around it.
+        assert!(!board.reveal(&vec![1, 1]));
+        expected[4].state = CellState::Revealed;
+        same_cells(&board, &expected);
+        assert!(!board.reveal(&vec![2, 0]));
+        for index in [1, 2, 5] {
+            expected[index].state = CellState::Revealed;
+        }
+        same_cells(&board, &expected);
+
+        // Flags come and go without touching the mines underneath.
+        assert_eq!(board.toggle_flag(&vec![0, 0]), Some(CellState::Flagged));
+        expected[0].state = CellState::Flagged;
+        same_cells(&board, &expected);
+        assert_eq!(board.revealed_count(), 4);
+        assert_eq!(board.flag_count(), 1);
+        assert_eq!(board.adjacent_flags(&vec![1, 1]), 1);
+
+        // Revealing a mine reports it, like it always did.
+        assert!(board.reveal(&vec![2, 2]));
+        expected[8].state = CellState::Revealed;
+        same_cells(&board, &expected);
+    }
+
+    #[test]
+    fn test_hidden_and_flagged_neighbors_partition() {
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        board.reveal(&vec![1, 1]); // A "1", so nothing else opens.
+        board.reveal(&vec![1, 0]); // Also a "1".
+        board.toggle_flag(&vec![0, 0]);
+        board.toggle_flag(&vec![2, 2]);
+
+        let center = vec![1, 1];
+        let mut hidden = board.hidden_neighbors(&center);
+        let mut flagged = board.flagged_neighbors(&center);
+        hidden.sort();
+        flagged.sort();
+
+        assert_eq!(flagged, vec![vec![0, 0], vec![2, 2]]);
+        assert_eq!(
+            hidden,
+            vec![vec![0, 1], vec![0, 2], vec![1, 2], vec![2, 0], vec![2, 1]]
+        );
+        // Together with the one revealed neighbor, that's all 8.
+        assert_eq!(hidden.len() + flagged.len() + 1, 8);
+    }
+
+    #[test]
+    fn test_new_with_exclusions_keeps_zone_mine_free() {
+        // Exclude the 3x3 block in the middle of a 5x5 board.
+        let exclude: Vec<Coordinates> = (1..4)
+            .flat_map(|x| (1..4).map(move |y| vec![x, y]))
+            .collect();
+
+        for seed in 0..10 {
+            let board = Board::new_with_exclusions(vec![5, 5], 12, &exclude, seed).unwrap();
+            assert_eq!(board.num_mines(), 12);
+            for coords in &exclude {
+                let index = to_index(coords, &board.dimensions);
+                assert_ne!(board.cell_at(index).kind, CellKind::Mine);
+            }
+        }
+
+        // Only 16 cells are left outside the zone.
+        assert_eq!(
+            Board::new_with_exclusions(vec![5, 5], 17, &exclude, 0).err(),
+            Some(BoardError::TooManyMines {
+                requested: 17,
+                available: 16
+            })
+        );
+
+        // Exclusions that aren't on the board are reported.
+        assert_eq!(
+            Board::new_with_exclusions(vec![5, 5], 3, &[vec![1, 1, 1]], 0).err(),
+            Some(BoardError::WrongCoordinateCount {
+                expected: 2,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            Board::new_with_exclusions(vec![5, 5], 3, &[vec![5, 0]], 0).err(),
+            Some(BoardError::OutOfBounds { coords: vec![5, 0] })
+        );
+    }
+
+    #[test]
+    fn test_same_layout_ignores_progress() {
+        let mines = [vec![0, 0], vec![2, 1]];
+        let untouched = Board::from_mines(vec![3, 3], &mines);
+        let mut played = Board::from_mines(vec![3, 3], &mines);
+        played.reveal(&vec![1, 1]);
+        played.toggle_flag(&vec![0, 0]);
+        assert!(untouched.same_layout(&played));
+
+        // A different mine position, or a different shape, is a different layout.
+        let moved = Board::from_mines(vec![3, 3], &[vec![0, 0], vec![2, 2]]);
+        assert!(!untouched.same_layout(&moved));
+        let reshaped = Board::from_mines(vec![9, 1], &[vec![0, 0], vec![5, 0]]);
+        assert!(!untouched.same_layout(&reshaped));
+    }
+
+    #[test]
+    fn test_reveal_all_unflagged_with_wrong_flag_hits_mine() {
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        // Flag the wrong cell, leaving the mine unflagged.
+        board.toggle_flag(&vec![2, 2]);
+
+        assert_eq!(
+            board.reveal_all_unflagged(),
+            RevealOutcome::HitMine(vec![0, 0])
+        );
+        assert_eq!(board.cell_at(0).state, CellState::Revealed);
+        assert_eq!(board.cell_at(8).state, CellState::Flagged);
+    }
+
+    #[test]
+    fn test_counts_above_255_neither_wrap_nor_collide() {
+        // A 6D cell with a mine on every one of its 728 neighbors.
+        let dimensions = vec![3; 6];
+        let center = vec![1; 6];
+        let mines: Vec<Coordinates> = range_iter(&vec![0; 6], &vec![2; 6])
+            .filter(|cell| *cell != center)
+            .collect();
+        let board = Board::from_mines(dimensions, &mines);
+        let grid = board.adjacency_grid();
+        assert_eq!(grid[board.index_of(&center)], 728);
+        assert_eq!(board.verify_integrity(), Ok(()));
+
+        // Beyond what a `u16` holds, counts stop just short of the sentinel.
+        assert_eq!(saturating_count(1 << 20), MINE_SENTINEL - 1);
+        assert_eq!(saturating_count(300), 300);
+    }
+
+    #[test]
+    fn test_set_mine_never_counts_up_to_the_sentinel() {
+        // A real saturated count takes 11 dimensions and far too long to
+        // build in a test, so fake one: as far as `set_mine` knows, (1,1)
+        // already touches more mines than a `u16` can count.
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let center = board.index_of(&vec![1, 1]);
+        board.counts[center] = MINE_SENTINEL - 1;
+
+        // Rather than stepping onto the sentinel, the count is redone.
+        board.set_mine(&vec![2, 2], true).unwrap();
+        assert_eq!(board.adjacency_grid()[center], 2);
+        assert_eq!(board.verify_integrity(), Ok(()));
+
+        board.counts[center] = MINE_SENTINEL - 1;
+        board.set_mine(&vec![0, 0], false).unwrap();
+        assert_eq!(board.adjacency_grid()[center], 1);
+        assert_eq!(board.verify_integrity(), Ok(()));
+    }
+
+    #[test]
+    fn test_adjacency_grid_matches_fixture() {
+        // The 3x3 fixture with mines at (0,0) and (2,2).
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0], vec![2, 2]]);
+        let m = MINE_SENTINEL;
+        #[rustfmt::skip]
+        let expected = vec![
+            m, 1, 0,
+            1, 2, 1,
+            0, 1, m,
+        ];
+        assert_eq!(board.adjacency_grid(), expected);
+    }
+
+    #[test]
+    fn test_brute_force_adjacency_matches_fast_counts() {
+        for seed in 0..8 {
+            let mut board = Board::new_seeded(vec![5, 4, 3], 12, seed).unwrap();
+            assert_eq!(board.brute_force_adjacency(), board.adjacency_grid());
+
+            board.set_adjacency(Adjacency::Orthogonal);
+            assert_eq!(board.brute_force_adjacency(), board.adjacency_grid());
+
+            // The short wrapping axis makes both wrap directions land on
+            // the same cell, which must still only count once.
+            board.set_wrapping_axes(vec![true, false, true]);
+            assert_eq!(board.brute_force_adjacency(), board.adjacency_grid());
+
+            board.set_adjacency(Adjacency::Moore);
+            assert_eq!(board.brute_force_adjacency(), board.adjacency_grid());
+        }
+
+        let mut strip = Board::new_seeded(vec![2, 6], 4, 3).unwrap();
+        strip.set_wrapping_axes(vec![true, true]);
+        assert_eq!(strip.brute_force_adjacency(), strip.adjacency_grid());
+    }
+
+    #[test]
+    fn test_cached_strides_match_free_functions() {
+        let dimensions = vec![2, 3, 4];
+        let mut board = Board::from_mines(dimensions.clone(), &[]);
+        for index in 0..24 {
+            let coords = to_coords(index, &dimensions);
+            assert_eq!(board.coords_of(index), coords);
+            assert_eq!(board.index_of(&coords), to_index(&coords, &dimensions));
+            assert_eq!(board.index_of(&coords), index);
+        }
+
+        // Reshaping must refresh the cache.
+        board.reshape(vec![4, 6]).unwrap();
+        for index in 0..24 {
+            assert_eq!(board.coords_of(index), to_coords(index, &[4, 6]));
+        }
+    }
+
+    #[test]
+    fn test_over_flagged_when_flags_outnumber_mines() {
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0], vec![2, 2]]);
+        board.toggle_flag(&vec![0, 0]);
+        board.toggle_flag(&vec![1, 1]);
+        // As many flags as mines is fine, even with one of them wrong.
+        assert!(!board.over_flagged());
+
+        board.toggle_flag(&vec![2, 1]);
+        assert!(board.over_flagged());
+        board.toggle_flag(&vec![2, 1]);
+        assert!(!board.over_flagged());
+    }
+
+    #[test]
+    fn test_state_queries_by_coordinate() {
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        board.toggle_flag(&vec![0, 0]);
+        board.reveal(&vec![1, 1]);
+
+        assert!(board.is_flagged(&vec![0, 0]));
+        assert!(!board.is_revealed(&vec![0, 0]));
+        assert!(board.is_revealed(&vec![1, 1]));
+        assert!(!board.is_flagged(&vec![1, 1]));
+        assert_eq!(board.cell_state(&vec![2, 2]), Some(CellState::Hidden));
+
+        // Out of bounds, or with the wrong number of axes.
+        assert_eq!(board.cell_state(&vec![3, 0]), None);
+        assert_eq!(board.cell_state(&vec![0, 0, 0]), None);
+        assert!(!board.is_flagged(&vec![0, 3]));
+        assert!(!board.is_revealed(&vec![1]));
+    }
+
+    #[test]
+    fn test_reveal_limited_caps_flood_fill() {
+        let mut board = Board::from_mines(vec![10, 10], &[]);
+        let center = vec![5, 5];
+
+        assert_eq!(board.reveal_limited(&center, 5), Ok(5));
+        assert_eq!(board.revealed_count(), 5);
+
+        // Breadth-first order means the patch hugs the click: every revealed
+        // cell is the center itself or one of its direct neighbors.
+        let mut near_center = board.neighbors_where(&center, |state| *state == CellState::Revealed);
+        near_center.push(center.clone());
+        assert_eq!(near_center.len(), 5);
+
+        // Nothing more is revealed by a zero budget.
+        assert_eq!(board.reveal_limited(&vec![0, 0], 0), Ok(0));
+
+        // Nor by coordinates off the board, whatever the budget.
+        assert_eq!(
+            board.reveal_limited(&vec![0, 0, 0], 10),
+            Err(BoardError::WrongCoordinateCount {
+                expected: 2,
+                actual: 3,
+            })
+        );
+        assert_eq!(
+            board.reveal_limited(&vec![0, 10], 10),
+            Err(BoardError::OutOfBounds {
+                coords: vec![0, 10],
+            })
+        );
+        assert_eq!(board.revealed_count(), 5);
+    }
+
+    #[test]
+    fn test_has_zero_cell() {
+        assert!(Board::from_mines(vec![3, 3], &[vec![0, 0]]).has_zero_cell());
+        // The center mine touches every other cell.
+        assert!(!Board::from_mines(vec![3, 3], &[vec![1, 1]]).has_zero_cell());
+    }
+
+    #[test]
+    fn test_new_with_zero_cell_always_has_opening() {
+        // A dense board, where plain random layouts often lack a zero cell.
+        for seed in 0..20 {
+            let board = Board::new_with_zero_cell(vec![6, 6], 18, seed).unwrap();
+            assert!(board.has_zero_cell());
+            assert_eq!(board.num_mines(), 18);
+        }
+    }
+
+    #[test]
+    fn test_mine_report_classifies_mines_and_flags() {
+        let mut board = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![3, 3]]);
+        board.toggle_flag(&vec![0, 0]); // Correct.
+        board.toggle_flag(&vec![2, 0]); // Wrong: not a mine.
+        board.reveal(&vec![3, 3]); // The missed mine that ended the game.
+
+        let report = board.mine_report();
+        assert_eq!(report.flagged_correct, vec![vec![0, 0]]);
+        assert_eq!(report.unflagged, vec![vec![3, 3]]);
+        assert_eq!(report.wrong_flags, vec![vec![2, 0]]);
+    }
+
+    #[test]
+    fn test_reveal_limit_guards_large_cascade() {
+        let mut board = Board::from_mines(vec![12, 12, 12], &[]);
+        board.set_reveal_limit(Some(50));
+
+        assert_eq!(
+            board.try_reveal(&vec![6, 6, 6]),
+            Err(BoardError::RevealLimitExceeded { limit: 50 })
+        );
+        assert_eq!(board.revealed_count(), 50);
+
+        // Plain `reveal` respects the guard too, just without the report.
+        assert!(!board.reveal(&vec![0, 0, 0]));
+        assert_eq!(board.revealed_count(), 100);
+
+        // Lifting the guard lets the rest of the region open normally.
+        board.set_reveal_limit(None);
+        assert_eq!(board.try_reveal(&vec![11, 11, 11]), Ok(false));
+        assert_eq!(board.revealed_count(), 12 * 12 * 12);
+    }
+
+    #[test]
+    fn test_neighbors_uses_board_dimensions() {
+        let board = Board::from_mines(vec![3, 3], &[]);
+        let mut neighbors = board.neighbors(&vec![1, 1]);
+        neighbors.sort();
+        assert_eq!(
+            neighbors,
+            vec![
+                vec![0, 0],
+                vec![0, 1],
+                vec![0, 2],
+                vec![1, 0],
+                vec![1, 2],
+                vec![2, 0],
+                vec![2, 1],
+                vec![2, 2],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_new_rejects_overflowing_dimensions() {
+        // 1000^8 = 10^24 cells, far beyond what a `usize` can count.
+        assert_eq!(
+            Board::new(vec![1000; 8], 10).err(),
+            Some(BoardError::TooLarge {
+                max_cells: DEFAULT_MAX_CELLS
+            })
+        );
+    }
+
+    #[test]
+    fn test_new_respects_configurable_limits() {
+        assert_eq!(
+            Board::new_with_max_cells(vec![10, 10], 1, 50).err(),
+            Some(BoardError::TooLarge { max_cells: 50 })
+        );
+        assert!(Board::new_with_max_cells(vec![5, 10], 1, 50).is_ok());
+        // A limit above the default is honored too: this board is past
+        // `DEFAULT_MAX_CELLS`, so only the mine count can reject it (which
+        // also keeps the test from allocating it).
+        let past_default = vec![2, DEFAULT_MAX_CELLS];
+        assert_eq!(
+            Board::new_with_max_cells(past_default.clone(), usize::MAX, 4 * DEFAULT_MAX_CELLS)
+                .err(),
+            Some(BoardError::TooManyMines {
+                requested: usize::MAX,
+                available: 2 * DEFAULT_MAX_CELLS,
+            })
+        );
+        assert_eq!(
+            Board::new(past_default, 1).err(),
+            Some(BoardError::TooLarge {
+                max_cells: DEFAULT_MAX_CELLS
+            })
+        );
+        assert_eq!(
+            Board::new(vec![2, 2], 5).err(),
+            Some(BoardError::TooManyMines {
+                requested: 5,
+                available: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_reveal_many_stops_at_first_mine() {
+        // A single row with a mine at x=2. (1,0) is a '1', so clicking it
+        // reveals only itself.
+        let mut board = Board::from_mines(vec![5, 1], &[vec![2, 0]]);
+        let outcome = board.reveal_many(&[vec![1, 0], vec![2, 0], vec![3, 0]]);
+
+        assert_eq!(outcome, RevealOutcome::HitMine(vec![2, 0]));
+        assert!(board.is_revealed(&vec![1, 0]));
+        assert!(board.is_revealed(&vec![2, 0]));
+        // Processing stopped at the mine, so (3,0) was never clicked.
+        assert!(!board.is_revealed(&vec![3, 0]));
+    }
+
+    #[test]
+    fn test_reveal_many_collects_flood_fills() {
+        let mut board = Board::from_mines(vec![5, 1], &[vec![2, 0]]);
+        let outcome = board.reveal_many(&[vec![0, 0], vec![4, 0]]);
+
+        // (0,0) is a zero and cascades to (1,0); (4,0) cascades to (3,0).
+        assert_eq!(
+            outcome,
+            RevealOutcome::Safe {
+                revealed: vec![vec![0, 0], vec![1, 0], vec![4, 0], vec![3, 0]],
+                adjacent_mines: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_reveal_many_reports_no_op_for_revealed_cells() {
+        let mut board = Board::from_mines(vec![5, 1], &[vec![2, 0]]);
+
+        assert_eq!(
+            board.reveal_many(&[vec![1, 0]]),
+            RevealOutcome::Safe {
+                revealed: vec![vec![1, 0]],
+                adjacent_mines: Some(1)
+            }
+        );
+        // Clicking the same cell again changes nothing.
+        assert_eq!(board.reveal_many(&[vec![1, 0]]), RevealOutcome::NoOp);
+
+        // Neither do flagged cells, nor a board with nothing left to open.
+        board.toggle_flag(&vec![3, 0]);
+        assert_eq!(board.reveal_many(&[vec![3, 0]]), RevealOutcome::NoOp);
+        board.toggle_flag(&vec![2, 0]);
+        board.toggle_flag(&vec![3, 0]);
+        assert!(matches!(
+            board.reveal_all_unflagged(),
+            RevealOutcome::Safe { .. }
+        ));
+        assert_eq!(board.reveal_all_unflagged(), RevealOutcome::NoOp);
+    }
+
+    #[test]
+    fn test_reveal_many_reports_the_clicked_number() {
+        // The center of this board touches two mines.
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0], vec![2, 2]]);
+        assert_eq!(
+            board.reveal_many(&[vec![1, 1]]),
+            RevealOutcome::Safe {
+                revealed: vec![vec![1, 1]],
+                adjacent_mines: Some(2)
+            }
+        );
+
+        // A zero cascades, so there is no single number to report.
+        let mut board = Board::from_mines(vec![4, 1], &[vec![3, 0]]);
+        assert!(matches!(
+            board.reveal_many(&[vec![0, 0]]),
+            RevealOutcome::Safe {
+                adjacent_mines: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_orthogonal_adjacency_changes_numbers() {
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0], vec![2, 1]]);
+        assert_eq!(board.neighbors(&vec![1, 1]).len(), 8);
+        assert_eq!(board.adjacency_grid()[4], 2);
+
+        board.set_adjacency(Adjacency::Orthogonal);
+        let mut neighbors = board.neighbors(&vec![1, 1]);
+        neighbors.sort();
+        assert_eq!(
+            neighbors,
+            vec![vec![0, 1], vec![1, 0], vec![1, 2], vec![2, 1]]
+        );
+        // Only the mine at (2,1) is across a face from the center.
+        assert_eq!(board.adjacency_grid()[4], 1);
+        assert_eq!(board.adjacency_grid()[1], 1);
+    }
+
+    #[test]
+    fn test_chord_reveals_only_orthogonal_neighbors() {
+        // The center sees the mine at (1,0) orthogonally; the mines on the
+        // corners are not its neighbors in this mode.
+        let mines = [vec![1, 0], vec![0, 2], vec![2, 2]];
+        let mut board = Board::from_mines(vec![3, 3], &mines);
+        board.set_adjacency(Adjacency::Orthogonal);
+        board.reveal(&vec![1, 1]);
+        board.toggle_flag(&vec![1, 0]);
+
+        assert_eq!(
+            board.chord(&vec![1, 1]),
+            RevealOutcome::Safe {
+                revealed: vec![vec![0, 1], vec![2, 1], vec![1, 2]],
+                adjacent_mines: None
+            }
+        );
+        // The corners stay hidden, mines or not.
+        assert!(!board.is_revealed(&vec![0, 0]));
+        assert!(!board.is_revealed(&vec![2, 0]));
+        assert!(!board.is_revealed(&vec![0, 2]));
+        // Nothing is left to chord.
+        assert_eq!(board.chord(&vec![1, 1]), RevealOutcome::NoOp);
+    }
+
+    #[test]
+    fn test_chord_needs_matching_flags() {
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        board.reveal(&vec![1, 1]);
+        // No flags yet, so the '1' can't be chorded.
+        assert_eq!(board.chord(&vec![1, 1]), RevealOutcome::NoOp);
+        // A wrong flag is trusted, and the chord hits the mine.
+        board.toggle_flag(&vec![2, 2]);
+        assert_eq!(board.chord(&vec![1, 1]), RevealOutcome::HitMine(vec![0, 0]));
+    }
+
+    #[test]
+    fn test_flood_stops_at_flags_by_default() {
+        // Row ". . . 1 *": the flag on (1,0) walls off (2,0) and (3,0).
+        let mut board = Board::from_mines(vec![5, 1], &[vec![4, 0]]);
+        assert_eq!(board.flood_policy(), FloodPolicy::StopAtFlags);
+        board.toggle_flag(&vec![1, 0]);
+
+        assert!(!board.reveal(&vec![0, 0]));
+        assert_eq!(board.revealed_count(), 1);
+        assert!(board.is_flagged(&vec![1, 0]));
+        assert!(!board.is_revealed(&vec![2, 0]));
+        assert!(!board.is_revealed(&vec![3, 0]));
+    }
+
+    #[test]
+    fn test_flood_through_flags_when_ignoring_them() {
+        let mut board = Board::from_mines(vec![5, 1], &[vec![4, 0]]);
+        board.set_flood_policy(FloodPolicy::IgnoreFlags);
+        board.toggle_flag(&vec![1, 0]);
+
+        assert!(!board.reveal(&vec![0, 0]));
+        // The wrong flag is cleared and the fill carries on to the '1'.
+        assert_eq!(board.revealed_count(), 4);
+        assert!(board.is_revealed(&vec![1, 0]));
+        assert!(board.is_revealed(&vec![3, 0]));
+        assert!(!board.is_revealed(&vec![4, 0]));
+        // A flag on the clicked cell itself still blocks the reveal.
+        let mut board = Board::from_mines(vec![5, 1], &[vec![4, 0]]);
+        board.set_flood_policy(FloodPolicy::IgnoreFlags);
+        board.toggle_flag(&vec![0, 0]);
+        board.reveal(&vec![0, 0]);
+        assert_eq!(board.revealed_count(), 0);
+    }
+
+    #[test]
+    fn test_safe_cells() {
+        let board = Board::new(vec![10, 10], 10).unwrap();
+        assert_eq!(board.safe_cells(), 90);
+        assert_eq!(Board::from_mines(vec![2, 2], &[]).safe_cells(), 4);
+    }
+
+    #[test]
+    fn test_reshape_keeps_mine_indices() {
+        // Mines at flat indices 0 and 5 of a 4x4 board.
+        let mut board = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![1, 1]]);
+        let mines_before = board.mine_bitset();
+        assert_eq!(board.adjacency_grid()[4], 2);
+
+        board.reshape(vec![2, 8]).unwrap();
+        assert_eq!(board.dimensions(), &[2, 8]);
+        assert_eq!(board.dimension(1), Some(8));
+        assert_eq!(board.mine_bitset(), mines_before);
+        // Index 5 is now (1,2) and index 4 is (0,2): they touch now.
+        assert!(board.is_mine(&vec![1, 2]).unwrap());
+        assert_eq!(board.adjacency_grid()[4], 1);
+        assert_eq!(board.adjacency_grid()[2], 2);
+
+        assert_eq!(
+            board.reshape(vec![3, 5]),
+            Err(BoardError::CellCountMismatch {
+                expected: 16,
+                actual: Some(15)
+            })
+        );
+        assert_eq!(board.dimensions(), &[2, 8]);
+    }
+
+    #[test]
+    fn test_adjacent_flags() {
+        let mut board = Board::from_mines(vec![3, 3], &[]);
+        board.toggle_flag(&vec![0, 0]);
+        board.toggle_flag(&vec![1, 0]);
+        assert_eq!(board.adjacent_flags(&vec![1, 1]), 2);
+        // A cell's own flag doesn't count.
+        assert_eq!(board.adjacent_flags(&vec![0, 0]), 1);
+
+        // Orthogonally, (1,1) no longer touches the corner (0,0).
+        board.set_adjacency(Adjacency::Orthogonal);
+        assert_eq!(board.adjacent_flags(&vec![1, 1]), 1);
+    }
+
+    #[test]
+    fn test_kind_counts_match_generation() {
+        for seed in 0..5 {
+            let board = Board::new_seeded(vec![9, 7, 3], 40, seed).unwrap();
+            let (mines, empty) = board.kind_counts();
+            assert_eq!(mines, board.num_mines());
+            assert_eq!(mines, 40);
+            assert_eq!(mines + empty, 9 * 7 * 3);
+        }
+    }
+
+    #[test]
+    fn test_local_density_finds_clusters() {
+        // All 9 mines packed into the 3x3 corner of a 10x10 board.
+        let mines: Vec<Coordinates> = (0..9).map(|i| vec![i % 3, i / 3]).collect();
+        let board = Board::from_mines(vec![10, 10], &mines);
+
+        // Around (1,1), the 3x3 window is all mines.
+        assert_eq!(board.local_density(&vec![1, 1], 1), 1.0);
+        assert!(board.local_density(&vec![0, 0], 2) > 0.9);
+        assert_eq!(board.local_density(&vec![7, 7], 2), 0.0);
+        // A wider window around the corner dilutes it.
+        assert!(board.local_density(&vec![1, 1], 3) < 0.5);
+    }
+
+    #[test]
+    fn test_new_with_rng_is_deterministic() {
+        use rand::rngs::mock::StepRng;
+
+        let first = Board::new_with_rng(vec![8, 8], 10, &mut StepRng::new(3, 7)).unwrap();
+        let second = Board::new_with_rng(vec![8, 8], 10, &mut StepRng::new(3, 7)).unwrap();
+        assert!(first.same_layout(&second));
+        assert_eq!(first.kind_counts(), (10, 54));
+
+        // Seeded boards go through the very same code.
+        let mut rng = StdRng::seed_from_u64(42);
+        let injected = Board::new_with_rng(vec![8, 8], 10, &mut rng).unwrap();
+        assert!(injected.same_layout(&Board::new_seeded(vec![8, 8], 10, 42).unwrap()));
+    }
+
+    #[test]
+    fn test_preview_reveal_matches_reveal() {
+        let mut board = Board::new_seeded(vec![12, 12], 20, 8).unwrap();
+        board.toggle_flag(&vec![5, 5]);
+
+        for index in 0..board.cell_count() {
+            let coords = to_coords(index, board.dimensions());
+            let mut copy = board.clone();
+            let before = copy.revealed_count();
+            copy.reveal(&coords);
+            let uncovered = copy.revealed_count() - before;
+            assert_eq!(
+                board.preview_reveal(&coords),
+                Ok(uncovered),
+                "at {coords:?}"
+            );
+        }
+        // Previewing changed nothing.
+        assert_eq!(board.revealed_count(), 0);
+        assert_eq!(board.preview_reveal(&vec![5, 5]), Ok(0));
+
+        // Coordinates off the board are reported, not quietly previewed.
+        assert_eq!(
+            board.preview_reveal(&vec![5, 5, 0]),
+            Err(BoardError::WrongCoordinateCount {
+                expected: 2,
+                actual: 3,
+            })
+        );
+        assert_eq!(
+            board.preview_reveal(&vec![12, 0]),
+            Err(BoardError::OutOfBounds {
+                coords: vec![12, 0],
+            })
+        );
+    }
+
+    #[test]
+    fn test_wrapping_one_axis_changes_numbers() {
+        // Mines on the left edge, at (0,0) and (0,3).
+        let mut board = Board::from_mines(vec![5, 4], &[vec![0, 0], vec![0, 3]]);
+        let count = |board: &Board, coords: Coordinates| {
+            board.adjacency_grid()[to_index(&coords, board.dimensions())]
+        };
+        assert_eq!(count(&board, vec![4, 1]), 0);
+
+        board.set_wrapping_axes(vec![true, false]);
+        assert_eq!(board.wrapping_axes(), vec![true, false]);
+        // Axis 0 wraps, so the right edge touches the mine on the left edge...
+        assert_eq!(count(&board, vec![4, 1]), 1);
+        assert_eq!(board.neighbors(&vec![4, 1]).len(), 8);
+        // ...but axis 1 doesn't, so (0,0) and (0,3) stay apart.
+        assert_eq!(count(&board, vec![1, 0]), 1);
+        assert_eq!(board.neighbors(&vec![2, 0]).len(), 5);
+
+        // The right edge now touches mines, so a cascade stops there.
+        board.reveal(&vec![2, 1]);
+        assert!(board.is_revealed(&vec![4, 2]));
+        assert_eq!(count(&board, vec![4, 2]), 1);
+    }
+
+    #[test]
+    fn test_reveal_stepwise_matches_reveal() {
+        let board = Board::new_seeded(vec![10, 10], 8, 3).unwrap();
+        let zero = (0..100)
+            .find(|&i| board.cell_at(i).kind == CellKind::Empty { adjacent_mines: 0 })
+            .unwrap();
+        let coords = to_coords(zero, board.dimensions());
+
+        let mut expected = board.clone();
+        let outcome = expected.reveal_many(std::slice::from_ref(&coords));
+        let RevealOutcome::Safe { revealed, .. } = outcome else {
+            panic!("a zero cell is safe");
+        };
+
+        let mut stepwise = board.clone();
+        let steps: Vec<Coordinates> = stepwise.reveal_stepwise(&coords).unwrap().collect();
+        assert_eq!(steps, revealed);
+        assert_eq!(stepwise.revealed_count(), revealed.len());
+        for c in &steps {
+            assert_eq!(stepwise.revealed_at_move(c), Some(1));
+        }
+    }
+
+    #[test]
+    fn test_reveal_stepwise_can_be_cancelled() {
+        let mut board = Board::from_mines(vec![10, 1], &[]);
+        let mut steps = board.reveal_stepwise(&vec![0, 0]).unwrap();
+        assert_eq!(steps.next(), Some(vec![0, 0]));
+        assert_eq!(steps.next(), Some(vec![1, 0]));
+        drop(steps);
+
+        assert_eq!(board.revealed_count(), 2);
+        assert!(!board.is_revealed(&vec![2, 0]));
+    }
+
+    #[test]
+    fn test_new_safe_border_keeps_the_edge_clear() {
+        // A 5x5 board has a 3x3 interior, so nine mines fill it completely.
+        for seed in 0..5 {
+            let board = Board::new_safe_border(vec![5, 5], 6, seed).unwrap();
+            assert_eq!(board.kind_counts().0, 6);
+            for index in 0..25 {
+                let coords = to_coords(index, &[5, 5]);
+                if coords.iter().any(|&c| c == 0 || c == 4) {
+                    assert_eq!(board.is_mine(&coords), Some(false), "at {coords:?}");
+                }
+            }
+        }
+        let full = Board::new_safe_border(vec![5, 5], 9, 0).unwrap();
+        assert_eq!(full.mines_in_region(&vec![1, 1], &vec![3, 3]), 9);
+
+        assert_eq!(
+            Board::new_safe_border(vec![5, 5], 10, 0).err(),
+            Some(BoardError::TooManyMines {
+                requested: 10,
+                available: 9
+            })
+        );
+        // Too thin for any interior at all.
+        assert!(Board::new_safe_border(vec![2, 8], 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_new_weighted_concentrates_mines() {
+        // The left half of a 20x20 board is 50 times as likely to get a mine.
+        let weight = |c: &Coordinates| if c[0] < 10 { 50.0 } else { 1.0 };
+        let board = Board::new_weighted(vec![20, 20], 60, weight, 9).unwrap();
+        assert_eq!(board.kind_counts().0, 60);
+        let left = board.mines_in_region(&vec![0, 0], &vec![9, 19]);
+        assert!(left >= 50, "only {left} of 60 mines on the left");
+
+        // A zero weight keeps a cell free, even when that leaves no choice.
+        let only_top_row = |c: &Coordinates| if c[1] == 0 { 1.0 } else { 0.0 };
+        let board = Board::new_weighted(vec![4, 4], 4, only_top_row, 1).unwrap();
+        assert_eq!(board.mines_in_region(&vec![0, 0], &vec![3, 0]), 4);
+        assert_eq!(
+            Board::new_weighted(vec![4, 4], 5, only_top_row, 1).err(),
+            Some(BoardError::TooManyMines {
+                requested: 5,
+                available: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_reveal_stepwise_follows_the_flood_rules() {
+        // Frozen cells, flags under `IgnoreFlags` and the reveal limit all
+        // shape the cascade; stepping must give exactly what planning does.
+        let mut board = Board::from_mines(vec![8, 8], &[vec![7, 7]]);
+        board.set_frozen(&vec![2, 2], true).unwrap();
+        board.toggle_flag(&vec![4, 1]);
+        board.set_flood_policy(FloodPolicy::IgnoreFlags);
+        for limit in [None, Some(1), Some(7), Some(30)] {
+            board.set_reveal_limit(limit);
+            let planned: Vec<Coordinates> = board
+                .plan_flood(0, limit.unwrap_or(usize::MAX))
+                .revealed
+                .into_iter()
+                .map(|index| board.coords_of(index))
+                .collect();
+
+            let mut stepwise = board.clone();
+            let steps: Vec<Coordinates> = stepwise.reveal_stepwise(&vec![0, 0]).unwrap().collect();
+            assert_eq!(steps, planned, "limit {limit:?}");
+            assert_eq!(stepwise.revealed_count(), planned.len());
+        }
+    }
+
+    #[test]
+    fn test_reveal_stepwise_only_explores_as_far_as_it_reveals() {
+        let mut board = Board::from_mines(vec![100, 100], &[]);
+        let mut steps = board.reveal_stepwise(&vec![50, 50]).unwrap();
+        // Nothing is planned up front...
+        assert_eq!(steps.pending.len(), 1);
+        // ...and revealing the start only queues its 8 neighbors, not the
+        // 10,000 cells of the whole cascade.
+        assert_eq!(steps.next(), Some(vec![50, 50]));
+        assert_eq!(steps.pending.len(), 8);
+        assert_eq!(steps.included.count_ones(), 9);
+        drop(steps);
+
+        assert_eq!(board.revealed_count(), 1);
+        assert!(!board.is_revealed(&vec![50, 51]));
+    }
+
+    #[test]
+    fn test_reveal_stepwise_rejects_coordinates_off_the_board() {
+        let mut board = Board::from_mines(vec![10, 1], &[]);
+        assert!(matches!(
+            board.reveal_stepwise(&vec![0]),
+            Err(BoardError::WrongCoordinateCount {
+                expected: 2,
+                actual: 1,
+            })
+        ));
+        assert!(matches!(
+            board.reveal_stepwise(&vec![10, 0]),
+            Err(BoardError::OutOfBounds { .. })
+        ));
+        assert_eq!(board.revealed_count(), 0);
+    }
+
+    #[test]
+    fn test_reveal_without_cascade_opens_one_cell() {
+        let mut board = Board::from_mines(vec![5, 5], &[vec![4, 4]]);
+        assert!(board.cascade());
+        board.set_cascade(false);
+
+        // (0,0) is a zero, but only it is revealed.
+        assert!(!board.reveal(&vec![0, 0]));
+        assert_eq!(board.revealed_count(), 1);
+        assert_eq!(board.preview_reveal(&vec![1, 1]), Ok(1));
+
+        board.set_cascade(true);
+        board.reveal(&vec![1, 1]);
+        assert_eq!(board.revealed_count(), 24);
+    }
+
+    #[test]
+    fn test_neighbor_summary() {
+        // Mines at (0,0) and (2,0); the center (1,1) sees all eight cells.
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0], vec![2, 0]]);
+        board.reveal(&vec![1, 0]);
+        board.reveal(&vec![2, 1]);
+        board.toggle_flag(&vec![0, 0]);
+        // A question mark still counts as hidden.
+        board.cycle_mark(&vec![0, 2]);
+        board.cycle_mark(&vec![0, 2]);
+        assert_eq!(board.cell_state(&vec![0, 2]), Some(CellState::Questioned));
+
+        let summary = board.neighbor_summary(&vec![1, 1]);
+        assert_eq!(
+            summary,
+            NeighborSummary {
+                hidden: 5,
+                flagged: 1,
+                revealed: 2,
+                mines_if_known: 0,
+            }
+        );
+
+        // Once the mine at (2,0) is uncovered, it counts as known.
+        board.reveal(&vec![2, 0]);
+        let summary = board.neighbor_summary(&vec![1, 1]);
+        assert_eq!(summary.revealed, 3);
+        assert_eq!(summary.mines_if_known, 1);
+        assert_eq!(summary.hidden, 4);
+    }
+
+    #[test]
+    fn test_nearest_mine_distance_radiates_outwards() {
+        let board = Board::from_mines(vec![7, 5], &[vec![2, 1]]);
+        let distances = board.nearest_mine_distance();
+        for (index, &distance) in distances.iter().enumerate() {
+            let coords = to_coords(index, board.dimensions());
+            let expected = coords[0].abs_diff(2).max(coords[1].abs_diff(1));
+            assert_eq!(distance, expected, "at {coords:?}");
+        }
+        assert_eq!(distances[to_index(&vec![6, 4], &[7, 5])], 4);
+
+        // With a second mine, every cell takes the closer of the two.
+        let board = Board::from_mines(vec![7, 1], &[vec![0, 0], vec![6, 0]]);
+        assert_eq!(board.nearest_mine_distance(), vec![0, 1, 2, 3, 2, 1, 0]);
+
+        let board = Board::from_mines(vec![2, 2], &[]);
+        assert_eq!(board.nearest_mine_distance(), vec![usize::MAX; 4]);
+    }
+
+    #[test]
+    fn test_recalculate_region_matches_full_recompute() {
+        let mut board = Board::new_seeded(vec![12, 10, 3], 40, 6).unwrap();
+        // Edit mines directly, bypassing `set_mine`, within one step of
+        // (5, 5, 1).
+        for index in [
+            to_index(&vec![5, 5, 1], &[12, 10, 3]),
+            to_index(&vec![6, 4, 0], &[12, 10, 3]),
+        ] {
+            let is_mine = board.mines.get(index);
+            board.mines.set(index, !is_mine);
+        }
+        let mut full = board.clone();
+        full.calculate_adjacent_mines();
+
+        board.recalculate_region(&vec![5, 5, 1], 2).unwrap();
+        assert_eq!(board.adjacency_grid(), full.adjacency_grid());
+
+        // The same holds when the region wraps around an edge.
+        let mut board = Board::from_mines(vec![6, 6], &[vec![2, 2]]);
+        board.set_wrapping_axes(vec![true, true]);
+        board.mines.set(to_index(&vec![0, 0], &[6, 6]), true);
+        let mut full = board.clone();
+        full.calculate_adjacent_mines();
+        board.recalculate_region(&vec![0, 0], 1).unwrap();
+        assert_eq!(board.adjacency_grid(), full.adjacency_grid());
+    }
+
+    #[test]
+    fn test_reveal_all_shows_everything() {
+        let mut board = Board::new_seeded(vec![5, 4, 3], 12, 8).unwrap();
+        board.toggle_flag(&vec![0, 0, 0]);
+        board.reveal_all();
+        assert!(board.cells().all(|c| c.state == CellState::Revealed));
+        assert_eq!(board.revealed_at_move(&vec![4, 3, 2]), Some(1));
+        assert_eq!(board.kind_counts().0, 12);
+    }
+
+    #[test]
+    fn test_frozen_cells_ignore_the_player() {
+        let mut board = Board::from_mines(vec![5, 5], &[vec![4, 4]]);
+        board.set_frozen(&vec![2, 2], true).unwrap();
+        assert!(board.is_frozen(&vec![2, 2]));
+        assert!(!board.is_frozen(&vec![9, 9]));
+
+        // (2,2) sits in the middle of the cascade from (0,0), but stays
+        // hidden while everything around it opens.
+        board.reveal(&vec![0, 0]);
+        assert!(!board.is_revealed(&vec![2, 2]));
+        assert!(board.is_revealed(&vec![2, 3]));
+        assert_eq!(board.revealed_count(), 23);
+
+        // It can't be revealed, flagged or marked directly either.
+        board.reveal(&vec![2, 2]);
+        assert_eq!(board.toggle_flag(&vec![2, 2]), Some(CellState::Hidden));
+        board.cycle_mark(&vec![2, 2]);
+        board.reveal_all_unflagged();
+        assert_eq!(board.cell_state(&vec![2, 2]), Some(CellState::Hidden));
+
+        board.set_frozen(&vec![2, 2], false).unwrap();
+        assert!(!board.reveal(&vec![2, 2]));
+        assert!(board.is_revealed(&vec![2, 2]));
+    }
+
+    #[test]
+    fn test_diff_lists_changed_cells() {
+        let before = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![3, 3]]);
+        let mut after = before.clone();
+        assert!(before.diff(&after).is_empty());
+
+        after.toggle_flag(&vec![3, 3]);
+        assert_eq!(before.diff(&after), vec![(vec![3, 3], CellState::Flagged)]);
+        // The other way round, the delta hides the cell again.
+        assert_eq!(after.diff(&before), vec![(vec![3, 3], CellState::Hidden)]);
+
+        // A cascade shows up as one entry per revealed cell.
+        after.reveal(&vec![3, 0]);
+        assert_eq!(before.diff(&after).len(), after.revealed_count() + 1);
+    }
+
+    #[test]
+    fn test_reset_states_keeps_the_layout() {
+        let mut board = Board::new_seeded(vec![6, 6], 6, 4).unwrap();
+        let original = board.clone();
+        let mine = (0..36)
+            .map(|i| to_coords(i, board.dimensions()))
+            .find(|c| board.is_mine(c) == Some(true))
+            .unwrap();
+        board.toggle_flag(&mine);
+        board.reveal_all_unflagged();
+        assert!(board.revealed_count() > 0);
+
+        board.reset_states();
+        assert!(board.cells().all(|c| c.state == CellState::Hidden));
+        assert!(board.same_layout(&original));
+        assert_eq!(board.adjacency_grid(), original.adjacency_grid());
+        assert_eq!(board.revealed_at_move(&vec![0, 0]), None);
+
+        // The next reveal counts as the board's first again.
+        board.reveal(&vec![0, 0]);
+        assert_eq!(board.revealed_at_move(&vec![0, 0]), Some(1));
+    }
+
+    #[test]
+    fn test_verify_integrity() {
+        let mut board = Board::new_seeded(vec![6, 5, 4], 30, 11).unwrap();
+        assert_eq!(board.verify_integrity(), Ok(()));
+        // Changing the geometry recomputes the counts, so they still agree.
+        board.set_adjacency(Adjacency::Orthogonal);
+        board.set_wrapping_axes(vec![true, false, true]);
+        assert_eq!(board.verify_integrity(), Ok(()));
+
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0], vec![2, 2]]);
+        assert_eq!(board.verify_integrity(), Ok(()));
+        board.counts[4] = 1;
+        assert_eq!(
+            board.verify_integrity(),
+            Err(BoardError::AdjacentCountMismatch {
+                coords: vec![1, 1],
+                expected: 2,
+                actual: 1
+            })
+        );
+        board.num_mines = 3;
+        assert_eq!(
+            board.verify_integrity(),
+            Err(BoardError::MineCountMismatch {
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_mines_in_region_counts_sub_box() {
+        let mines = [vec![0, 0], vec![1, 2], vec![3, 3], vec![2, 1]];
+        let board = Board::from_mines(vec![4, 4], &mines);
+
+        assert_eq!(board.mines_in_region(&vec![0, 0], &vec![1, 1]), 1);
+        assert_eq!(board.mines_in_region(&vec![1, 1], &vec![2, 2]), 2);
+        assert_eq!(board.mines_in_region(&vec![0, 0], &vec![3, 3]), 4);
+        // A box reaching past the board only counts what's on it.
+        assert_eq!(board.mines_in_region(&vec![3, 3], &vec![9, 9]), 1);
+        // An inverted box is empty.
+        assert_eq!(board.mines_in_region(&vec![2, 0], &vec![1, 3]), 0);
+    }
+
+    #[test]
+    fn test_max_quadrant_density_is_respected() {
+        // 16 mines on 8x8: each 4x4 quadrant would hold 4 on average.
+        let board = Board::new_with_max_quadrant_density(vec![8, 8], 16, 0.375, 7).unwrap();
+        for (min, max) in [
+            (vec![0, 0], vec![3, 3]),
+            (vec![4, 0], vec![7, 3]),
+            (vec![0, 4], vec![3, 7]),
+            (vec![4, 4], vec![7, 7]),
+        ] {
+            assert!(board.mines_in_region(&min, &max) <= 6);
+        }
+    }
+
+    #[test]
+    fn test_coords_of_wrong_length_are_rejected() {
+        let mut board = Board::from_mines(vec![3, 3, 3], &[vec![0, 0, 0]]);
+        let short = vec![1, 2];
+
+        assert_eq!(
+            board.validate_coords(&short),
+            Err(BoardError::WrongCoordinateCount {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            board.validate_coords(&vec![1, 3, 0]),
+            Err(BoardError::OutOfBounds {
+                coords: vec![1, 3, 0]
+            })
+        );
+        assert_eq!(board.validate_coords(&vec![2, 2, 2]), Ok(()));
+
+        assert!(board.try_reveal(&short).is_err());
+        assert_eq!(board.revealed_count(), 0);
+    }
+
+    #[test]
+    fn test_set_mine_updates_neighbor_counts() {
+        let mut board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let before = board.adjacency_grid();
+
+        board.set_mine(&vec![2, 2], true).unwrap();
+        let after = board.adjacency_grid();
+        for neighbor in board.neighbors(&vec![2, 2]) {
+            let index = to_index(&neighbor, board.dimensions());
+            assert_eq!(after[index], before[index] + 1);
+        }
+        assert_eq!(board.num_mines(), 2);
+        assert_eq!(
+            board.adjacency_grid(),
+            Board::from_mines(vec![3, 3], &[vec![0, 0], vec![2, 2]]).adjacency_grid()
+        );
+
+        // Removing the original mine gives (0,0) a count of its own.
+        board.set_mine(&vec![0, 0], false).unwrap();
+        assert_eq!(
+            board.adjacency_grid(),
+            Board::from_mines(vec![3, 3], &[vec![2, 2]]).adjacency_grid()
+        );
+        assert_eq!(board.num_mines(), 1);
+    }
+
+    #[test]
+    fn test_is_mine() {
+        let board = Board::from_mines(vec![3, 3], &[vec![1, 2]]);
+        assert_eq!(board.is_mine(&vec![1, 2]), Some(true));
+        assert_eq!(board.is_mine(&vec![0, 0]), Some(false));
+        assert_eq!(board.is_mine(&vec![3, 0]), None);
+        // Looking doesn't reveal anything.
+        assert_eq!(board.revealed_count(), 0);
+    }
+
+    #[test]
+    fn test_cycle_mark_follows_configured_order() {
+        let mut board = Board::from_mines(vec![2, 2], &[]);
+        let coords = vec![0, 0];
+        let mut sequence = Vec::new();
+        for _ in 0..3 {
+            board.cycle_mark(&coords);
+            sequence.push(board.cell_state(&coords).unwrap());
+        }
+        assert_eq!(
+            sequence,
+            vec![CellState::Flagged, CellState::Questioned, CellState::Hidden]
+        );
+
+        board.set_mark_cycle(MarkCycle::QuestionThenFlag);
+        sequence.clear();
+        for _ in 0..3 {
+            board.cycle_mark(&coords);
+            sequence.push(board.cell_state(&coords).unwrap());
+        }
+        assert_eq!(
+            sequence,
+            vec![CellState::Questioned, CellState::Flagged, CellState::Hidden]
+        );
+    }
+
+    #[test]
+    fn test_question_marks_do_not_block_reveals() {
+        let mut board = Board::from_mines(vec![3, 3], &[]);
+        board.set_mark_cycle(MarkCycle::QuestionThenFlag);
+        board.cycle_mark(&vec![2, 2]);
+
+        // The cascade from the far corner still opens the questioned cell.
+        board.reveal(&vec![0, 0]);
+        assert_eq!(board.revealed_count(), 9);
+    }
+
+    #[test]
+    fn test_new_no_guess_is_solvable() {
+        let board = Board::new_no_guess(vec![6, 6], 6, 11).unwrap();
+        assert_eq!(board.num_mines(), 6);
+        assert_eq!(board.mine_bitset().count_ones(), 6);
+        assert_eq!(guesses_needed(&board), 1);
+
+        // The same seed always gives the same board.
+        let again = Board::new_no_guess(vec![6, 6], 6, 11).unwrap();
+        assert!(board.same_layout(&again));
+    }
+
+    #[test]
+    fn test_spread_placement_has_fewer_touching_mines() {
+        // Counts each pair of touching mines twice, which is fine for a
+        // comparison.
+        fn touching_pairs(board: &Board) -> usize {
+            let mines = board.mine_bitset();
+            (0..board.cell_count())
+                .filter(|&i| mines.get(i))
+                .map(|i| count_adjacent_mines(i, &board.geometry(), &mines) as usize)
+                .sum()
+        }
+
+        let (mut uniform, mut spread) = (0, 0);
+        for seed in 0..10 {
+            let board = Board::new_with_placement(vec![16, 16], 40, Placement::Uniform, seed);
+            uniform += touching_pairs(&board.unwrap());
+            let board = Board::new_with_placement(vec![16, 16], 40, Placement::Spread, seed);
+            let board = board.unwrap();
+            assert_eq!(board.mine_bitset().count_ones(), 40);
+            spread += touching_pairs(&board);
+        }
+        assert!(spread < uniform);
+    }
+
+    #[test]
+    fn test_revealed_at_move_numbers_reveals() {
+        // Mine at (3,3): the cascade from (0,0) opens most of the board.
+        let mut board = Board::from_mines(vec![4, 4], &[vec![3, 3]]);
+        board.toggle_flag(&vec![2, 3]);
+        board.reveal(&vec![0, 0]);
+        board.reveal(&vec![0, 0]); // Already revealed: not a new reveal.
+        board.toggle_flag(&vec![2, 3]);
+        board.reveal(&vec![2, 3]);
+
+        // Every cell of the flood fill shares the first number...
+        assert_eq!(board.revealed_at_move(&vec![0, 0]), Some(1));
+        assert_eq!(board.revealed_at_move(&vec![2, 2]), Some(1));
+        assert_eq!(board.revealed_at_move(&vec![3, 2]), Some(1));
+        // ...and the cell the flag kept out of it came later.
+        assert_eq!(board.revealed_at_move(&vec![2, 3]), Some(2));
+        assert_eq!(board.revealed_at_move(&vec![3, 3]), None);
+    }
+
+    #[test]
+    fn test_toggle_flag() {
+        let mut board = Board::new(vec![2, 2], 0).unwrap();
+        let coords = vec![0, 0];
+
+        // Initially hidden
+        assert_eq!(board.cell_at(0).state, CellState::Hidden);
+
+        // Toggle to flagged
+        board.toggle_flag(&coords);
+        assert_eq!(board.cell_at(0).state, CellState::Flagged);
+
+        // Toggle back to hidden
+        board.toggle_flag(&coords);
+        assert_eq!(board.cell_at(0).state, CellState::Hidden);
+    }
+
+    #[test]
+    fn test_toggle_flag_returns_new_state() {
+        let mut board = Board::from_mines(vec![2, 2], &[]);
+        assert_eq!(board.toggle_flag(&vec![1, 1]), Some(CellState::Flagged));
+        assert_eq!(board.toggle_flag(&vec![1, 1]), Some(CellState::Hidden));
+        assert_eq!(board.toggle_flag(&vec![2, 0]), None);
+
+        board.reveal(&vec![0, 0]);
+        assert_eq!(board.toggle_flag(&vec![0, 0]), Some(CellState::Revealed));
+    }
+
+    #[test]
     fn test_reveal_mine() {
-        let mut board = Board::new(vec![2, 2], 1);
+        let mut board = Board::new(vec![2, 2], 1).unwrap();
         // Find the mine
         let mine_index = board
-            .cells
-            .iter()
+            .cells()
             .position(|c| c.kind == CellKind::Mine)
             .unwrap();
         let mine_coords = to_coords(mine_index, &board.dimensions);
@@ -224,13 +3957,13 @@ mod tests {
         // Reveal the mine
         let is_mine = board.reveal(&mine_coords);
         assert!(is_mine);
-        assert_eq!(board.cells[mine_index].state, CellState::Revealed);
+        assert_eq!(board.cell_at(mine_index).state, CellState::Revealed);
     }
 
     #[test]
     fn test_reveal_empty_cell() {
-        let mut board = Board::new(vec![3, 3], 0);
-        board.cells[0].kind = CellKind::Mine; // Place a mine at (0,0)
+        let mut board = Board::new(vec![3, 3], 0).unwrap();
+        board.mines.set(0, true); // Place a mine at (0,0)
         board.calculate_adjacent_mines();
         let coords = vec![1, 1]; // A cell with 1 adjacent mine
 
@@ -238,13 +3971,13 @@ mod tests {
         let is_mine = board.reveal(&coords);
         assert!(!is_mine);
         let index = to_index(&coords, &board.dimensions);
-        assert_eq!(board.cells[index].state, CellState::Revealed);
+        assert_eq!(board.cell_at(index).state, CellState::Revealed);
     }
 
     #[test]
     fn test_flood_fill_reveal() {
-        let mut board = Board::new(vec![3, 3], 0);
-        board.cells[0].kind = CellKind::Mine; // Mine at (0,0)
+        let mut board = Board::new(vec![3, 3], 0).unwrap();
+        board.mines.set(0, true); // Mine at (0,0)
         board.calculate_adjacent_mines();
 
         // Reveal a cell with 0 adjacent mines
@@ -253,7 +3986,7 @@ mod tests {
 
         // All cells except the mine at (0,0) should be revealed.
         // The mine is at index 0. All others should be revealed.
-        for (i, cell) in board.cells.iter().enumerate() {
+        for (i, cell) in board.cells().enumerate() {
             if i == 0 {
                 // The mine should not be revealed
                 assert_ne!(cell.state, CellState::Revealed);
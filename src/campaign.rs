@@ -0,0 +1,183 @@
+// src/campaign.rs
+
+//! The `campaign` module strings games together into a sequence of levels.
+//!
+//! A campaign is just a list of level configurations (dimensions and mine
+//! count) plus a pointer to the level being played. Only one `Game` exists at
+//! a time: the next level's board is generated when the player gets there, so
+//! a long campaign costs no more memory than a single game.
+
+use crate::error::BoardError;
+use crate::game::{Game, GameState};
+
+/// A sequence of levels, played one after the other.
+pub struct Campaign {
+    // Every level's dimensions and mine count, in playing order.
+    levels: Vec<(Vec<usize>, usize)>,
+
+    // The index of the level being played. Equal to `levels.len()` once the
+    // campaign is complete.
+    current: usize,
+
+    // The game for the current level, or `None` once the campaign is
+    // complete.
+    game: Option<Game>,
+}
+
+impl Campaign {
+    /// Creates a campaign and starts its first level.
+    ///
+    /// # Arguments
+    ///
+    /// * `levels` - The dimensions and mine count of every level, in playing
+    ///   order. An empty list gives a campaign that is complete from the start.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `BoardError` from `Game::new` if the first level's board
+    /// can't be created. Later levels are only checked when they are reached.
+    pub fn new(levels: Vec<(Vec<usize>, usize)>) -> Result<Self, BoardError> {
+        let mut campaign = Self {
+            levels,
+            current: 0,
+            game: None,
+        };
+        campaign.start_level()?;
+        Ok(campaign)
+    }
+
+    /// Returns the game for the current level, or `None` once every level
+    /// has been won.
+    pub fn current_game(&self) -> Option<&Game> {
+        self.game.as_ref()
+    }
+
+    /// Returns the game for the current level, for playing it.
+    pub fn current_game_mut(&mut self) -> Option<&mut Game> {
+        self.game.as_mut()
+    }
+
+    /// Returns the index of the current level, counting from zero. Once the
+    /// campaign is complete, this is the number of levels.
+    pub fn level(&self) -> usize {
+        self.current
+    }
+
+    /// Returns how many levels the campaign has.
+    pub fn num_levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns `true` once every level has been won.
+    pub fn is_complete(&self) -> bool {
+        self.current == self.levels.len()
+    }
+
+    /// Moves on to the next level, but only if the current one was won.
+    ///
+    /// A lost level has to be retried with `restart_level`; a level still in
+    /// progress has to be finished first.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the campaign moved on (possibly to completion), `false` if
+    /// the current level wasn't won (or the campaign is already complete).
+    ///
+    /// # Errors
+    ///
+    /// Returns the `BoardError` from `Game::new` if the next level's board
+    /// can't be created. The campaign then stays on that level, without a
+    /// game, and `current_game` returns `None`.
+    pub fn advance_on_win(&mut self) -> Result<bool, BoardError> {
+        let won = matches!(
+            self.game.as_ref().map(Game::state),
+            Some(GameState::Won { .. })
+        );
+        if !won {
+            return Ok(false);
+        }
+        self.current += 1;
+        self.start_level()?;
+        Ok(true)
+    }
+
+    /// Replaces the current level's game with a fresh one, e.g. after a loss.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `BoardError` from `Game::new` if the board can't be
+    /// created.
+    pub fn restart_level(&mut self) -> Result<(), BoardError> {
+        self.start_level()
+    }
+
+    /// Creates the game for the current level, or clears it if the campaign
+    /// is complete.
+    fn start_level(&mut self) -> Result<(), BoardError> {
+        self.game = None;
+        if let Some((dimensions, num_mines)) = self.levels.get(self.current) {
+            self.game = Some(Game::new(dimensions.clone(), *num_mines)?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::to_coords;
+
+    /// Reveals safe cells (peeking at the answer key) until the game is won.
+    fn win(game: &mut Game) {
+        while *game.state() == GameState::InProgress {
+            let board = game.board();
+            let coords = (0..board.cell_count())
+                .map(|i| to_coords(i, board.dimensions()))
+                .find(|c| board.is_mine(c) == Some(false) && !board.is_revealed(c))
+                .unwrap();
+            game.reveal(&coords).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_campaign_completes_after_winning_every_level() {
+        let mut campaign = Campaign::new(vec![(vec![4, 4], 2), (vec![5, 5, 2], 3)]).unwrap();
+        assert_eq!(campaign.num_levels(), 2);
+
+        // Nothing to advance to before the level is won.
+        assert!(!campaign.advance_on_win().unwrap());
+        assert_eq!(campaign.level(), 0);
+
+        win(campaign.current_game_mut().unwrap());
+        assert!(campaign.advance_on_win().unwrap());
+        assert_eq!(campaign.level(), 1);
+        let game = campaign.current_game().unwrap();
+        assert_eq!(game.board().dimensions(), &[5, 5, 2]);
+        assert!(!campaign.is_complete());
+
+        win(campaign.current_game_mut().unwrap());
+        assert!(campaign.advance_on_win().unwrap());
+        assert!(campaign.is_complete());
+        assert!(campaign.current_game().is_none());
+        assert!(!campaign.advance_on_win().unwrap());
+    }
+
+    #[test]
+    fn test_lost_level_must_be_restarted() {
+        let mut campaign = Campaign::new(vec![(vec![3, 3], 4)]).unwrap();
+        let game = campaign.current_game_mut().unwrap();
+        let mine = (0..9)
+            .map(|i| to_coords(i, &[3, 3]))
+            .find(|c| game.board().is_mine(c) == Some(true))
+            .unwrap();
+        game.reveal(&mine).unwrap();
+
+        assert!(!campaign.advance_on_win().unwrap());
+        campaign.restart_level().unwrap();
+        assert_eq!(
+            *campaign.current_game().unwrap().state(),
+            GameState::InProgress
+        );
+        assert_eq!(campaign.level(), 0);
+    }
+}
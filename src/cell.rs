@@ -4,7 +4,9 @@
 //!
 //! Each cell can be in various states, and can either be a mine or be empty.
 
-// The Cell struct represents a single cell on the board.
+// The Cell struct represents a single cell on the board. The board keeps
+// states, mines and counts apart to save memory, so a `Cell` is a copy put
+// together on demand by `Board::cell_at` and `Board::cells`.
 #[derive(Clone, Debug)]
 pub struct Cell {
     /// The state of the cell.
@@ -16,6 +18,7 @@ pub struct Cell {
 
 // CellState represents the visibility of a cell.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CellState {
     /// The cell is hidden from the player.
     Hidden,
@@ -23,6 +26,57 @@ pub enum CellState {
     Revealed,
     /// The cell has been flagged by the player as a potential mine.
     Flagged,
+    /// The player marked the cell with a question mark, as a note that it
+    /// might be a mine. Unlike a flag, a question mark doesn't protect the
+    /// cell: it still counts as hidden (see `CellState::is_hidden`).
+    Questioned,
+}
+
+impl CellState {
+    /// Returns `true` if the cell is covered and unprotected, i.e. hidden or
+    /// only question-marked.
+    ///
+    /// A question mark is just a note for the player, so reveals, flood
+    /// fills and solvers treat such cells exactly like plain hidden ones.
+    pub fn is_hidden(&self) -> bool {
+        matches!(self, CellState::Hidden | CellState::Questioned)
+    }
+}
+
+/// The order in which `Board::cycle_mark` steps through the player's marks.
+///
+/// Players disagree on whether question marks are useful, and on whether
+/// they should come before or after the flag, so this is configurable per
+/// board (see `Board::set_mark_cycle`). Every cycle starts and ends at
+/// `CellState::Hidden`; revealed cells are never changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MarkCycle {
+    /// The classic order: Hidden → Flagged → Questioned → Hidden.
+    #[default]
+    FlagThenQuestion,
+    /// Hidden → Questioned → Flagged → Hidden.
+    QuestionThenFlag,
+    /// No question marks at all: Hidden → Flagged → Hidden.
+    FlagOnly,
+}
+
+impl MarkCycle {
+    /// Returns the state that follows `state` in this cycle.
+    pub fn next(self, state: &CellState) -> CellState {
+        use CellState::*;
+        match (self, state) {
+            (_, Revealed) => Revealed,
+            (MarkCycle::FlagThenQuestion, Hidden) => Flagged,
+            (MarkCycle::FlagThenQuestion, Flagged) => Questioned,
+            (MarkCycle::FlagThenQuestion, Questioned) => Hidden,
+            (MarkCycle::QuestionThenFlag, Hidden) => Questioned,
+            (MarkCycle::QuestionThenFlag, Questioned) => Flagged,
+            (MarkCycle::QuestionThenFlag, Flagged) => Hidden,
+            // A question mark left over from another cycle is dropped.
+            (MarkCycle::FlagOnly, Hidden | Questioned) => Flagged,
+            (MarkCycle::FlagOnly, Flagged) => Hidden,
+        }
+    }
 }
 
 // CellKind represents the content of a cell.
@@ -31,7 +85,7 @@ pub enum CellKind {
     /// The cell is a mine.
     Mine,
     /// The cell is empty, and contains a count of adjacent mines.
-    Empty { adjacent_mines: u8 },
+    Empty { adjacent_mines: u16 },
 }
 
 impl Cell {
@@ -43,3 +97,12 @@ impl Cell {
         }
     }
 }
+
+// A "default" cell is the same as a freshly created one. Implementing
+// `Default` lets cells be used with APIs such as `Vec::resize_with` and
+// `std::mem::take`, and keeps `Cell::new` and `Cell::default` in agreement.
+impl Default for Cell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
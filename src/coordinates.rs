@@ -7,6 +7,12 @@
 //! vector, which is how the board's cells are stored. It also provides a way
 //! to iterate over the neighbors of a cell in N-dimensional space.
 
+use crate::board::checked_cell_count;
+use crate::error::ParseError;
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::sync::OnceLock;
+
 /// A type alias for N-dimensional coordinates.
 pub type Coordinates = Vec<usize>;
 
@@ -59,6 +65,265 @@ pub fn to_coords(mut index: usize, dimensions: &[usize]) -> Coordinates {
     coords
 }
 
+/// Converts a 1D index to N-dimensional coordinates, or returns `None` if the
+/// index lies beyond the end of the grid.
+///
+/// `to_coords` trusts its input: an index that is too large silently turns
+/// into coordinates outside the grid (on the last axis). Use this version
+/// for indices that come from outside, e.g. from a saved file.
+///
+/// # Arguments
+///
+/// * `index` - The 1D index.
+/// * `dimensions` - The dimensions of the N-dimensional grid.
+pub fn to_coords_checked(index: usize, dimensions: &[usize]) -> Option<Coordinates> {
+    let total = dimensions
+        .iter()
+        .try_fold(1_usize, |acc, &size| acc.checked_mul(size));
+    // A grid too large to count has room for every `usize` index.
+    if total.is_some_and(|total| index >= total) {
+        return None;
+    }
+    Some(to_coords(index, dimensions))
+}
+
+/// Re-keys a map from coordinates to flat indices (see `to_index`).
+///
+/// Hashing a flat `usize` is much cheaper than hashing a whole `Coordinates`
+/// vector, and index keys need no allocation, so per-cell maps in hot code
+/// (solvers, probability tables) should be keyed by index. This and
+/// `by_coords` convert at the boundary, where callers want coordinates.
+///
+/// # Arguments
+///
+/// * `map` - The coordinate-keyed entries, e.g. a `HashMap<Coordinates, V>`.
+/// * `dimensions` - The dimensions of the grid.
+pub fn by_index<V>(
+    map: impl IntoIterator<Item = (Coordinates, V)>,
+    dimensions: &[usize],
+) -> HashMap<usize, V> {
+    map.into_iter()
+        .map(|(coords, value)| (to_index(&coords, dimensions), value))
+        .collect()
+}
+
+/// Re-keys a map from flat indices to coordinates (see `to_coords`). This is
+/// the inverse of `by_index`.
+///
+/// # Arguments
+///
+/// * `map` - The index-keyed entries, e.g. a `HashMap<usize, V>`.
+/// * `dimensions` - The dimensions of the grid.
+pub fn by_coords<V>(
+    map: impl IntoIterator<Item = (usize, V)>,
+    dimensions: &[usize],
+) -> HashMap<Coordinates, V> {
+    map.into_iter()
+        .map(|(index, value)| (to_coords(index, dimensions), value))
+        .collect()
+}
+
+/// Returns every neighbor offset for a grid with `num_dimensions` axes.
+///
+/// An offset is a vector of `-1`, `0` or `1` per axis; adding it to a cell's
+/// coordinates gives one of its neighbors. There are `3^N - 1` of them: every
+/// combination except all zeros, which would be the cell itself.
+///
+/// The set depends only on the number of dimensions, so callers that visit
+/// many cells can compute it once and reuse it with `apply_offset`, instead
+/// of re-deriving it for every cell; `cached_neighbor_offsets` does exactly
+/// that, once per process. Variants such as orthogonal-only neighborhoods are
+/// just a filter over this list.
+///
+/// The offsets are listed in base-3 counting order with the first axis
+/// changing fastest, the same order `get_neighbors` returns neighbors in.
+pub fn neighbor_offsets(num_dimensions: usize) -> Vec<Vec<i32>> {
+    let count = 3_usize.pow(num_dimensions as u32);
+    let center = (count - 1) / 2;
+
+    (0..count)
+        .filter(|&i| i != center)
+        .map(|i| {
+            // Read `i` as a base-3 number, one digit per axis, and map each
+            // digit 0/1/2 to the offset -1/0/1.
+            let mut n = i;
+            (0..num_dimensions)
+                .map(|_| {
+                    let offset = (n % 3) as i32 - 1;
+                    n /= 3;
+                    offset
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The largest number of dimensions whose offset table is cached by
+/// `cached_neighbor_offsets`. At 3^12 offsets of 12 axes each, the biggest
+/// table already takes about 25 MB; boards with more axes are too slow to
+/// play anyway.
+const MAX_CACHED_DIMENSIONS: usize = 12;
+
+/// The offset tables built so far, one per number of dimensions.
+static OFFSET_TABLES: [OnceLock<Vec<Vec<i32>>>; MAX_CACHED_DIMENSIONS + 1] =
+    [const { OnceLock::new() }; MAX_CACHED_DIMENSIONS + 1];
+
+/// Returns the same offsets as `neighbor_offsets`, but only builds each
+/// table once per process.
+///
+/// Every neighbor query needs the table, and the neighbor queries are the
+/// hottest code in the crate (adjacency counts, flood fills, solvers), so
+/// the table is built on first use and shared from then on. Only tables for
+/// up to `MAX_CACHED_DIMENSIONS` axes are kept; beyond that, a fresh one is
+/// built on every call.
+pub fn cached_neighbor_offsets(num_dimensions: usize) -> Cow<'static, [Vec<i32>]> {
+    match OFFSET_TABLES.get(num_dimensions) {
+        Some(table) => Cow::Borrowed(table.get_or_init(|| neighbor_offsets(num_dimensions))),
+        None => Cow::Owned(neighbor_offsets(num_dimensions)),
+    }
+}
+
+/// Adds `offset` to `coords`, returning `None` if the result leaves the grid.
+///
+/// # Arguments
+///
+/// * `coords` - The N-dimensional coordinates to start from.
+/// * `offset` - The per-axis change, e.g. from `neighbor_offsets`.
+/// * `dimensions` - The dimensions of the grid.
+pub fn apply_offset(
+    coords: &Coordinates,
+    offset: &[i32],
+    dimensions: &[usize],
+) -> Option<Coordinates> {
+    coords
+        .iter()
+        .zip(offset)
+        .zip(dimensions)
+        .map(|((&coord, &delta), &size)| {
+            // `checked_add_signed` fails instead of wrapping below zero, and
+            // the filter catches stepping past the far edge.
+            coord
+                .checked_add_signed(delta as isize)
+                .filter(|&moved| moved < size)
+        })
+        .collect()
+}
+
+/// Adds `offset` to `coords` on a grid whose edges wrap around on some axes.
+///
+/// On an axis where `wrap` is `true`, stepping past one edge comes back in
+/// at the opposite edge, as on a torus (or a cylinder, if only some axes
+/// wrap). On the other axes the grid ends as usual, and the result is `None`
+/// if the step leaves it. Missing entries in `wrap` count as `false`.
+///
+/// # Arguments
+///
+/// * `coords` - The N-dimensional coordinates to start from.
+/// * `offset` - The per-axis change, e.g. from `neighbor_offsets`.
+/// * `dimensions` - The dimensions of the grid.
+/// * `wrap` - For every axis, whether its edges wrap around.
+pub fn apply_offset_wrapping(
+    coords: &Coordinates,
+    offset: &[i32],
+    dimensions: &[usize],
+    wrap: &[bool],
+) -> Option<Coordinates> {
+    coords
+        .iter()
+        .zip(offset)
+        .zip(dimensions)
+        .enumerate()
+        .map(|(axis, ((&coord, &delta), &size))| {
+            if wrap.get(axis).copied().unwrap_or(false) {
+                let moved = (coord as i64 + delta as i64).rem_euclid(size as i64);
+                Some(moved as usize)
+            } else {
+                coord
+                    .checked_add_signed(delta as isize)
+                    .filter(|&moved| moved < size)
+            }
+        })
+        .collect()
+}
+
+/// Moves `coords` by `delta` steps along a single axis.
+///
+/// This is the building block for cursor and camera movement in front-ends:
+/// pressing "left" is `step(cursor, 0, -1, dimensions)`.
+///
+/// # Arguments
+///
+/// * `coords` - The N-dimensional coordinates to start from.
+/// * `axis` - Which axis to move along (0 for x, 1 for y, ...).
+/// * `delta` - How far to move; negative values move towards zero.
+/// * `dimensions` - The dimensions of the grid.
+///
+/// # Returns
+///
+/// The moved coordinates, or `None` if the move would leave the grid (or
+/// `axis` doesn't exist).
+pub fn step(
+    coords: &Coordinates,
+    axis: usize,
+    delta: i32,
+    dimensions: &[usize],
+) -> Option<Coordinates> {
+    let size = *dimensions.get(axis)?;
+    let moved = coords.get(axis)?.checked_add_signed(delta as isize)?;
+    if moved >= size {
+        return None;
+    }
+    let mut result = coords.clone();
+    result[axis] = moved;
+    Some(result)
+}
+
+/// Returns the cell halfway between `a` and `b`, rounding down on every axis.
+///
+/// Front-ends use this to center a camera between two points of interest.
+/// The midpoint of two cells on a grid is always on that grid too.
+///
+/// # Returns
+///
+/// The midpoint, or `None` if `a` and `b` have different numbers of
+/// components.
+pub fn midpoint(a: &Coordinates, b: &Coordinates) -> Option<Coordinates> {
+    if a.len() != b.len() {
+        return None;
+    }
+    // `(x + y) / 2` could overflow; this can't.
+    let point = a
+        .iter()
+        .zip(b)
+        .map(|(&x, &y)| x.min(y) + x.abs_diff(y) / 2)
+        .collect();
+    Some(point)
+}
+
+/// Interpolates linearly from `a` (at `t = 0`) to `b` (at `t = 1`), rounding
+/// to the nearest cell on every axis.
+///
+/// This is the building block for smooth camera transitions: step `t` from
+/// 0 to 1 over a few frames. `t` is clamped to `0..=1`, so the result always
+/// lies in the box spanned by `a` and `b`.
+///
+/// # Returns
+///
+/// The interpolated cell, or `None` if `a` and `b` have different numbers
+/// of components or `t` is NaN.
+pub fn lerp(a: &Coordinates, b: &Coordinates, t: f64) -> Option<Coordinates> {
+    if a.len() != b.len() || t.is_nan() {
+        return None;
+    }
+    let t = t.clamp(0.0, 1.0);
+    let point = a
+        .iter()
+        .zip(b)
+        .map(|(&x, &y)| (x as f64 + (y as f64 - x as f64) * t).round() as usize)
+        .collect();
+    Some(point)
+}
+
 /// Returns a list of valid neighbor coordinates for a given cell.
 ///
 /// This function explores all adjacent cells in an N-dimensional grid. An adjacent
@@ -74,46 +339,384 @@ pub fn to_coords(mut index: usize, dimensions: &[usize]) -> Coordinates {
 ///
 /// A `Vec<Coordinates>` containing the coordinates of all valid neighbors.
 pub fn get_neighbors(coords: &Coordinates, dimensions: &[usize]) -> Vec<Coordinates> {
-    let mut neighbors = Vec::new();
-    let num_dimensions = coords.len();
-    if num_dimensions == 0 {
-        return neighbors;
+    if coords.is_empty() {
+        return Vec::new();
     }
 
-    let num_neighbors_to_check = 3_u32.pow(num_dimensions as u32);
-    let center_index = (num_neighbors_to_check - 1) / 2;
+    cached_neighbor_offsets(coords.len())
+        .iter()
+        .filter_map(|offset| apply_offset(coords, offset, dimensions))
+        .collect()
+}
 
-    'outer: for i in 0..num_neighbors_to_check {
-        if i == center_index {
-            continue;
-        }
+/// Returns every neighbor direction of a cell, together with the neighbor in
+/// that direction, or `None` where the grid's edge cuts it off.
+///
+/// `get_neighbors` silently drops the neighbors beyond the edge; this keeps
+/// them, so a UI can tell which sides of a cell lie on the boundary and draw
+/// them, e.g. as a border. The directions are the offsets from
+/// `neighbor_offsets`, in the same order.
+///
+/// # Arguments
+///
+/// * `coords` - The N-dimensional coordinates of the cell.
+/// * `dimensions` - The dimensions of the board.
+pub fn get_neighbors_with_validity(
+    coords: &Coordinates,
+    dimensions: &[usize],
+) -> Vec<(Vec<i32>, Option<Coordinates>)> {
+    if coords.is_empty() {
+        return Vec::new();
+    }
 
-        let mut temp_coords = coords.clone();
-        let mut n = i;
+    cached_neighbor_offsets(coords.len())
+        .iter()
+        .map(|offset| (offset.clone(), apply_offset(coords, offset, dimensions)))
+        .collect()
+}
 
-        for j in 0..num_dimensions {
-            let offset = (n % 3) as i32 - 1;
-            n /= 3;
+/// Returns the neighbors of a cell whose number of dimensions is known at
+/// compile time, without any heap allocation.
+///
+/// `get_neighbors` builds a `Vec` per neighbor, which adds up in hot 2D and
+/// 3D code. With fixed-size arrays, every neighbor is a plain `Copy` value
+/// and the offsets are computed on the fly, so iterating costs nothing
+/// beyond the arithmetic. The neighbors come out
+/// in the same order as from `get_neighbors`. Use `get_neighbors` for boards
+/// whose number of dimensions is only known at runtime.
+///
+/// # Arguments
+///
+/// * `coords` - The coordinates of the cell, e.g. `[x, y]`.
+/// * `dimensions` - The dimensions of the board, e.g. `[width, height]`.
+pub fn get_neighbors_fixed<const N: usize>(
+    coords: [usize; N],
+    dimensions: [usize; N],
+) -> impl Iterator<Item = [usize; N]> {
+    let count = 3_usize.pow(N as u32);
+    let center = (count - 1) / 2;
 
-            // Check for underflow before applying the offset
-            if offset == -1 && temp_coords[j] == 0 {
-                continue 'outer;
+    (0..count)
+        .filter(move |&i| i != center)
+        .filter_map(move |i| {
+            // Same base-3 reading of `i` as in `neighbor_offsets`.
+            let mut n = i;
+            let mut neighbor = coords;
+            for (coord, &size) in neighbor.iter_mut().zip(&dimensions) {
+                let delta = (n % 3) as isize - 1;
+                n /= 3;
+                *coord = coord
+                    .checked_add_signed(delta)
+                    .filter(|&moved| moved < size)?;
             }
+            Some(neighbor)
+        })
+}
+
+/// Returns the flat indices of every neighbor of the cell at flat `index`.
+///
+/// This gives the same neighbors, in the same order, as `get_neighbors`
+/// followed by `to_index` on each result, but without building a coordinate
+/// vector per neighbor. Hot loops that only need indices (such as counting
+/// adjacent mines for every cell) should use this.
+///
+/// # Arguments
+///
+/// * `index` - The flat index of the cell.
+/// * `dimensions` - The dimensions of the board.
+pub fn get_neighbor_indices(index: usize, dimensions: &[usize]) -> Vec<usize> {
+    get_neighbor_indices_by_order(index, dimensions, dimensions.len())
+}
+
+/// Returns the flat indices of the neighbors of the cell at flat `index`
+/// that differ from it on at most `max_changed_axes` axes.
+///
+/// This is to `get_neighbors_by_order` what `get_neighbor_indices` is to
+/// `get_neighbors`: the same neighbors, in the same order, as flat indices.
+///
+/// # Arguments
+///
+/// * `index` - The flat index of the cell.
+/// * `dimensions` - The dimensions of the board.
+/// * `max_changed_axes` - How many coordinates a neighbor may differ in.
+pub fn get_neighbor_indices_by_order(
+    index: usize,
+    dimensions: &[usize],
+    max_changed_axes: usize,
+) -> Vec<usize> {
+    // For every axis: the cell's position along it, the axis size, and the
+    // stride (how far apart in the flat vector two steps along it are).
+    let mut stride = 1;
+    let axes: Vec<(usize, usize, usize)> = dimensions
+        .iter()
+        .map(|&size| {
+            let axis = (index / stride % size, size, stride);
+            stride *= size;
+            axis
+        })
+        .collect();
+
+    cached_neighbor_offsets(dimensions.len())
+        .iter()
+        .filter(|offset| offset.iter().filter(|&&d| d != 0).count() <= max_changed_axes)
+        .filter_map(|offset| {
+            offset
+                .iter()
+                .zip(&axes)
+                .try_fold(index, |acc, (&delta, &(position, size, stride))| {
+                    let moved = position
+                        .checked_add_signed(delta as isize)
+                        .filter(|&moved| moved < size)?;
+                    // `acc` always still contains `position * stride`, since
+                    // each axis is visited exactly once.
+                    Some(acc - position * stride + moved * stride)
+                })
+        })
+        .collect()
+}
+
+/// Returns the neighbors of a cell that differ from it on at most
+/// `max_changed_axes` axes.
+///
+/// This one parameter covers the whole family of neighborhoods:
+///
+/// * `1` gives the orthogonal ("von Neumann") neighbors only: 4 in 2D, 6 in 3D.
+/// * `2` adds the neighbors across an edge-diagonal: 18 in 3D.
+/// * `N` (the number of dimensions) or more gives every neighbor, exactly
+///   like `get_neighbors` (the "Moore" neighborhood).
+///
+/// # Arguments
+///
+/// * `coords` - The N-dimensional coordinates of the cell.
+/// * `dimensions` - The dimensions of the board.
+/// * `max_changed_axes` - How many coordinates a neighbor may differ in.
+pub fn get_neighbors_by_order(
+    coords: &Coordinates,
+    dimensions: &[usize],
+    max_changed_axes: usize,
+) -> Vec<Coordinates> {
+    cached_neighbor_offsets(coords.len())
+        .iter()
+        .filter(|offset| offset.iter().filter(|&&d| d != 0).count() <= max_changed_axes)
+        .filter_map(|offset| apply_offset(coords, offset, dimensions))
+        .collect()
+}
+
+/// Returns every cell within `radius` steps of `coords` along every axis
+/// (the N-dimensional square centered on the cell), except the cell itself.
+///
+/// A radius of `1` gives exactly the cells `get_neighbors` returns, and in
+/// the same order; larger radii look further out, e.g. for density
+/// statistics. The square is clipped to the grid.
+///
+/// # Arguments
+///
+/// * `coords` - The N-dimensional coordinates of the center cell.
+/// * `dimensions` - The dimensions of the grid.
+/// * `radius` - How far out to look along each axis.
+pub fn get_neighbors_radius(
+    coords: &Coordinates,
+    dimensions: &[usize],
+    radius: usize,
+) -> Vec<Coordinates> {
+    if coords.is_empty() {
+        return Vec::new();
+    }
+    let min: Coordinates = coords.iter().map(|&c| c.saturating_sub(radius)).collect();
+    let max: Coordinates = coords
+        .iter()
+        .zip(dimensions)
+        .map(|(&c, &size)| c.saturating_add(radius).min(size.saturating_sub(1)))
+        .collect();
+    range_iter(&min, &max)
+        .filter(|cell| cell != coords)
+        .collect()
+}
 
-            let new_coord = (temp_coords[j] as i32 + offset) as usize;
+/// Returns the neighbors of a cell on a grid whose edges wrap around on the
+/// axes where `wrap` is `true` (see `apply_offset_wrapping`).
+///
+/// Like `get_neighbors_by_order`, only neighbors differing on at most
+/// `max_changed_axes` axes are included; pass the number of dimensions to get
+/// them all. On a wrapping axis of size 1 or 2, stepping either way can lead
+/// back to the same cell: every neighbor is listed once, and the cell itself
+/// never is.
+///
+/// # Arguments
+///
+/// * `coords` - The N-dimensional coordinates of the cell.
+/// * `dimensions` - The dimensions of the grid.
+/// * `wrap` - For every axis, whether its edges wrap around.
+/// * `max_changed_axes` - How many coordinates a neighbor may differ in.
+pub fn get_neighbors_wrapping(
+    coords: &Coordinates,
+    dimensions: &[usize],
+    wrap: &[bool],
+    max_changed_axes: usize,
+) -> Vec<Coordinates> {
+    let offsets = cached_neighbor_offsets(coords.len());
+    let candidates = offsets
+        .iter()
+        .filter(|offset| offset.iter().filter(|&&d| d != 0).count() <= max_changed_axes)
+        .filter_map(|offset| apply_offset_wrapping(coords, offset, dimensions, wrap));
 
-            // Check for overflow
-            if new_coord >= dimensions[j] {
-                continue 'outer;
+    let mut neighbors: Vec<Coordinates> = Vec::new();
+    for neighbor in candidates {
+        if neighbor != *coords && !neighbors.contains(&neighbor) {
+            neighbors.push(neighbor);
+        }
+    }
+    neighbors
+}
+
+/// Iterates over every coordinate in the box from `min` to `max` inclusive.
+///
+/// Coordinates come out in the same order as their flat indices (see
+/// `to_index`): the first axis changes fastest. The box is empty if
+/// `min > max` on any axis, or if `min` and `max` have different lengths.
+///
+/// # Arguments
+///
+/// * `min` - The corner with the smallest value on every axis.
+/// * `max` - The corner with the largest value on every axis.
+pub fn range_iter(min: &Coordinates, max: &Coordinates) -> impl Iterator<Item = Coordinates> {
+    let empty = min.len() != max.len() || min.iter().zip(max).any(|(lo, hi)| lo > hi);
+    let min = min.clone();
+    let max = max.clone();
+    let mut next = (!empty).then(|| min.clone());
+
+    std::iter::from_fn(move || {
+        let current = next.take()?;
+        // Advance like an odometer: bump the first axis, and carry into the
+        // next axis whenever one wraps past its maximum.
+        let mut following = current.clone();
+        for axis in 0..following.len() {
+            if following[axis] < max[axis] {
+                following[axis] += 1;
+                next = Some(following);
+                break;
             }
+            following[axis] = min[axis];
+        }
+        Some(current)
+    })
+}
 
-            temp_coords[j] = new_coord;
+/// Returns the cells a click on `start` would reveal, without touching any
+/// board.
+///
+/// This follows the same rule as the board's reveal cascade: the clicked
+/// cell is always included, and every included cell for which `is_zero`
+/// holds spreads to all of its neighbors. Because it only needs a predicate,
+/// solvers can use it to simulate reveals on boards they only partly know.
+///
+/// # Arguments
+///
+/// * `start` - The clicked cell.
+/// * `dimensions` - The dimensions of the grid.
+/// * `is_zero` - Whether a cell has no adjacent mines, i.e. keeps the
+///   cascade going.
+///
+/// # Returns
+///
+/// The region in breadth-first order, starting with `start`.
+///
+/// # Panics
+///
+/// Panics if the grid has too many cells to count in a `usize`.
+pub fn flood_region(
+    start: &Coordinates,
+    dimensions: &[usize],
+    is_zero: impl Fn(&Coordinates) -> bool,
+) -> Vec<Coordinates> {
+    let total_cells =
+        checked_cell_count(dimensions, usize::MAX).expect("grid dimensions overflow usize");
+    let mut seen = vec![false; total_cells];
+    seen[to_index(start, dimensions)] = true;
+
+    let mut region = vec![start.clone()];
+    let mut queue = VecDeque::from([start.clone()]);
+    while let Some(coords) = queue.pop_front() {
+        if !is_zero(&coords) {
+            continue;
+        }
+        for neighbor in get_neighbors(&coords, dimensions) {
+            let index = to_index(&neighbor, dimensions);
+            if !seen[index] {
+                seen[index] = true;
+                region.push(neighbor.clone());
+                queue.push_back(neighbor);
+            }
         }
+    }
+    region
+}
+
+/// Parses comma-separated text such as `"3,4,1"` into coordinates.
+///
+/// This is the inverse of `format`, and is meant for command-line input and
+/// text save formats. Whitespace around each number is ignored, so `"3, 4, 1"`
+/// works too.
+///
+/// # Errors
+///
+/// * `ParseError::Empty` if the input is empty or only whitespace.
+/// * `ParseError::InvalidToken` if any part isn't a non-negative integer.
+pub fn parse(s: &str) -> Result<Coordinates, ParseError> {
+    if s.trim().is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    s.split(',')
+        .enumerate()
+        .map(|(position, token)| {
+            let token = token.trim();
+            token.parse().map_err(|_| ParseError::InvalidToken {
+                token: token.to_string(),
+                position,
+            })
+        })
+        .collect()
+}
+
+/// Formats coordinates as comma-separated text, e.g. `"3,4,1"`.
+///
+/// The output can always be read back with `parse`.
+pub fn format(coords: &Coordinates) -> String {
+    coords
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
 
-        neighbors.push(temp_coords);
+/// Returns the conventional name of an axis: `x`, `y`, `z`, `w`, and then
+/// `d4`, `d5`, ... for the dimensions that have no traditional letter.
+pub fn default_axis_label(axis: usize) -> String {
+    match axis {
+        0 => "x".to_string(),
+        1 => "y".to_string(),
+        2 => "z".to_string(),
+        3 => "w".to_string(),
+        _ => format!("d{axis}"),
     }
+}
 
-    neighbors
+/// Formats coordinates with a name for every axis, e.g. `"x=3, y=4, z=1"`.
+///
+/// This is meant for people rather than for parsing: in 3D and beyond, bare
+/// numbers quickly become confusing. Axes without a label in `labels` get
+/// their `default_axis_label`.
+pub fn format_labeled(coords: &Coordinates, labels: &[String]) -> String {
+    coords
+        .iter()
+        .enumerate()
+        .map(|(axis, c)| match labels.get(axis) {
+            Some(label) => format!("{label}={c}"),
+            None => format!("{}={c}", default_axis_label(axis)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 #[cfg(test)]
@@ -180,4 +783,346 @@ mod tests {
         let neighbors = get_neighbors(&coords, &dimensions);
         assert_eq!(neighbors.len(), 26);
     }
+
+    #[test]
+    fn test_parse_and_format_3d() {
+        let coords = parse("3, 4,1").unwrap();
+        assert_eq!(coords, vec![3, 4, 1]);
+        assert_eq!(format(&coords), "3,4,1");
+        assert_eq!(parse(&format(&coords)).unwrap(), coords);
+    }
+
+    #[test]
+    fn test_parse_empty_string() {
+        assert_eq!(parse(""), Err(ParseError::Empty));
+        assert_eq!(parse("   "), Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn test_parse_malformed_token() {
+        let error = parse("3,x,1").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::InvalidToken {
+                token: "x".to_string(),
+                position: 1
+            }
+        );
+        assert!(error.to_string().contains("\"x\""));
+
+        // Negative numbers and empty parts are rejected too.
+        assert!(parse("3,-1").is_err());
+        assert!(parse("3,,1").is_err());
+    }
+
+    #[test]
+    fn test_format_labeled() {
+        let labels = vec!["col".to_string(), "row".to_string()];
+        assert_eq!(format_labeled(&vec![3, 4, 1], &labels), "col=3, row=4, z=1");
+        assert_eq!(
+            format_labeled(&vec![0, 0, 0, 0, 7], &[]),
+            "x=0, y=0, z=0, w=0, d4=7"
+        );
+    }
+
+    #[test]
+    fn test_get_neighbor_indices_3x3_center() {
+        let mut indices = get_neighbor_indices(4, &[3, 3]);
+        indices.sort();
+        assert_eq!(indices, vec![0, 1, 2, 3, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_get_neighbor_indices_matches_get_neighbors() {
+        let dimensions = vec![4, 3, 2];
+        for index in 0..24 {
+            let expected: Vec<usize> = get_neighbors(&to_coords(index, &dimensions), &dimensions)
+                .iter()
+                .map(|n| to_index(n, &dimensions))
+                .collect();
+            assert_eq!(get_neighbor_indices(index, &dimensions), expected);
+        }
+    }
+
+    #[test]
+    fn test_neighbor_offsets_counts() {
+        let offsets_2d = neighbor_offsets(2);
+        let offsets_3d = neighbor_offsets(3);
+        assert_eq!(offsets_2d.len(), 8);
+        assert_eq!(offsets_3d.len(), 26);
+
+        for offset in offsets_2d.iter().chain(&offsets_3d) {
+            assert!(offset.iter().any(|&d| d != 0));
+            assert!(offset.iter().all(|&d| (-1..=1).contains(&d)));
+        }
+    }
+
+    #[test]
+    fn test_cached_neighbor_offsets_match_and_are_shared() {
+        for n in 0..=4 {
+            assert_eq!(*cached_neighbor_offsets(n), neighbor_offsets(n));
+        }
+        // A second call hands out the very same table.
+        let first = cached_neighbor_offsets(3);
+        let second = cached_neighbor_offsets(3);
+        assert!(matches!(first, Cow::Borrowed(_)));
+        assert!(std::ptr::eq(first.as_ptr(), second.as_ptr()));
+    }
+
+    #[test]
+    fn test_apply_offset_clips_at_edges() {
+        let dimensions = vec![3, 3];
+        assert_eq!(
+            apply_offset(&vec![1, 1], &[-1, 1], &dimensions),
+            Some(vec![0, 2])
+        );
+        assert_eq!(apply_offset(&vec![0, 1], &[-1, 0], &dimensions), None);
+        assert_eq!(apply_offset(&vec![2, 1], &[1, 0], &dimensions), None);
+    }
+
+    #[test]
+    fn test_flood_region_all_zero_covers_grid() {
+        let dimensions = vec![3, 3];
+        for index in 0..9 {
+            let start = to_coords(index, &dimensions);
+            let mut region = flood_region(&start, &dimensions, |_| true);
+            assert_eq!(region[0], start);
+            region.sort();
+            assert_eq!(region.len(), 9);
+            region.dedup();
+            assert_eq!(region.len(), 9);
+        }
+    }
+
+    #[test]
+    fn test_flood_region_stops_at_numbers() {
+        // Only the left column is zero: the cascade reveals it plus the
+        // middle column that borders it, but never reaches x=2.
+        let region = flood_region(&vec![0, 0], &[3, 3], |c| c[0] == 0);
+        assert_eq!(region.len(), 6);
+        assert!(region.iter().all(|c| c[0] < 2));
+
+        // A non-zero start reveals only itself.
+        assert_eq!(
+            flood_region(&vec![2, 2], &[3, 3], |c| c[0] == 0),
+            vec![vec![2, 2]]
+        );
+    }
+
+    #[test]
+    fn test_range_iter_sub_box() {
+        let cells: Vec<Coordinates> = range_iter(&vec![2, 3], &vec![3, 4]).collect();
+        assert_eq!(cells, vec![vec![2, 3], vec![3, 3], vec![2, 4], vec![3, 4]]);
+
+        // The order matches flat indices on a larger board.
+        let dimensions = [10, 10];
+        let indices: Vec<usize> = cells.iter().map(|c| to_index(c, &dimensions)).collect();
+        assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_range_iter_edge_cases() {
+        assert_eq!(range_iter(&vec![1, 1], &vec![1, 1]).count(), 1);
+        assert_eq!(range_iter(&vec![0, 0, 0], &vec![2, 1, 3]).count(), 24);
+        assert_eq!(range_iter(&vec![2, 0], &vec![1, 5]).count(), 0);
+        // Zero dimensions: a single, empty coordinate.
+        assert_eq!(range_iter(&vec![], &vec![]).count(), 1);
+    }
+
+    #[test]
+    fn test_step_within_bounds() {
+        let dimensions = vec![4, 3, 2];
+        assert_eq!(step(&vec![1, 1, 0], 0, 2, &dimensions), Some(vec![3, 1, 0]));
+        assert_eq!(
+            step(&vec![1, 1, 0], 1, -1, &dimensions),
+            Some(vec![1, 0, 0])
+        );
+        assert_eq!(step(&vec![1, 1, 0], 2, 1, &dimensions), Some(vec![1, 1, 1]));
+    }
+
+    #[test]
+    fn test_step_off_the_edge() {
+        let dimensions = vec![4, 3];
+        assert_eq!(step(&vec![0, 1], 0, -1, &dimensions), None);
+        assert_eq!(step(&vec![3, 1], 0, 1, &dimensions), None);
+        assert_eq!(step(&vec![1, 2], 1, 1, &dimensions), None);
+        // There is no third axis to move along.
+        assert_eq!(step(&vec![1, 1], 2, 1, &dimensions), None);
+    }
+
+    #[test]
+    fn test_get_neighbors_with_validity_on_a_corner() {
+        let dimensions = [3, 3];
+        let all = get_neighbors_with_validity(&vec![0, 0], &dimensions);
+        assert_eq!(all.len(), 8);
+
+        // Every direction with a step below zero is cut off by the edge.
+        let clipped: Vec<Vec<i32>> = all
+            .iter()
+            .filter(|(_, neighbor)| neighbor.is_none())
+            .map(|(offset, _)| offset.clone())
+            .collect();
+        assert_eq!(clipped, vec![[-1, -1], [0, -1], [1, -1], [-1, 0], [-1, 1]]);
+
+        // The rest are exactly the plain neighbors, in the same order.
+        let inside: Vec<Coordinates> = all.into_iter().filter_map(|(_, n)| n).collect();
+        assert_eq!(inside, get_neighbors(&vec![0, 0], &dimensions));
+    }
+
+    #[test]
+    fn test_midpoint() {
+        assert_eq!(midpoint(&vec![0, 0], &vec![4, 2]), Some(vec![2, 1]));
+        // Odd distances round down, whichever end comes first.
+        assert_eq!(midpoint(&vec![1, 5], &vec![4, 0]), Some(vec![2, 2]));
+        assert_eq!(
+            midpoint(&vec![usize::MAX, 0], &vec![usize::MAX - 2, 0]),
+            Some(vec![usize::MAX - 1, 0])
+        );
+        assert_eq!(midpoint(&vec![0, 0], &vec![4, 2, 1]), None);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let (a, b) = (vec![0, 10, 3], vec![8, 0, 3]);
+        assert_eq!(lerp(&a, &b, 0.0), Some(a.clone()));
+        assert_eq!(lerp(&a, &b, 1.0), Some(b.clone()));
+        assert_eq!(lerp(&a, &b, 0.25), Some(vec![2, 8, 3]));
+        // `t` is clamped to the segment between the two cells.
+        assert_eq!(lerp(&a, &b, 2.0), Some(b.clone()));
+        assert_eq!(lerp(&a, &b, -1.0), Some(a.clone()));
+        assert_eq!(lerp(&a, &b, f64::NAN), None);
+        assert_eq!(lerp(&a, &vec![1, 2], 0.5), None);
+    }
+
+    #[test]
+    fn test_to_coords_checked() {
+        let dimensions = [3, 4, 2];
+        for index in 0..24 {
+            assert_eq!(
+                to_coords_checked(index, &dimensions),
+                Some(to_coords(index, &dimensions))
+            );
+        }
+        assert_eq!(to_coords_checked(24, &dimensions), None);
+        assert_eq!(to_coords_checked(usize::MAX, &dimensions), None);
+        assert_eq!(to_coords_checked(0, &[0, 5]), None);
+    }
+
+    #[test]
+    fn test_get_neighbors_wrapping_one_axis() {
+        let dimensions = [5, 4];
+        let wrap = [true, false];
+
+        // On the left edge, axis 0 wraps around to x=4...
+        let mut neighbors = get_neighbors_wrapping(&vec![0, 1], &dimensions, &wrap, 2);
+        neighbors.sort();
+        let mut expected = vec![
+            vec![4, 0],
+            vec![4, 1],
+            vec![4, 2],
+            vec![0, 0],
+            vec![0, 2],
+            vec![1, 0],
+            vec![1, 1],
+            vec![1, 2],
+        ];
+        expected.sort();
+        assert_eq!(neighbors, expected);
+
+        // ...but on the top edge, axis 1 still clips.
+        let neighbors = get_neighbors_wrapping(&vec![2, 0], &dimensions, &wrap, 2);
+        assert_eq!(neighbors.len(), 5);
+        assert!(neighbors.iter().all(|n| n[1] <= 1));
+
+        // Without any wrapping, this is plain `get_neighbors`.
+        let plain = get_neighbors_wrapping(&vec![0, 1], &dimensions, &[false, false], 2);
+        assert_eq!(plain, get_neighbors(&vec![0, 1], &dimensions));
+    }
+
+    #[test]
+    fn test_get_neighbors_wrapping_small_axis() {
+        // On a wrapping axis of size 2, left and right are the same cell.
+        let neighbors = get_neighbors_wrapping(&vec![0], &[2], &[true], 1);
+        assert_eq!(neighbors, vec![vec![1]]);
+        // And on one of size 1, they are the cell itself.
+        assert!(get_neighbors_wrapping(&vec![0], &[1], &[true], 1).is_empty());
+    }
+
+    #[test]
+    fn test_get_neighbors_radius() {
+        let dimensions = [6, 5];
+        let center = vec![2, 2];
+        assert_eq!(
+            get_neighbors_radius(&center, &dimensions, 1),
+            get_neighbors(&center, &dimensions)
+        );
+        assert_eq!(get_neighbors_radius(&center, &dimensions, 2).len(), 24);
+        // Clipped at the corner: a 3x3 square minus the cell itself.
+        assert_eq!(get_neighbors_radius(&vec![0, 0], &dimensions, 2).len(), 8);
+        assert!(get_neighbors_radius(&center, &dimensions, 0).is_empty());
+    }
+
+    #[test]
+    fn test_get_neighbors_fixed_matches_general_path() {
+        let dimensions = [4, 3];
+        for index in 0..12 {
+            let coords = to_coords(index, &dimensions);
+            let fixed: Vec<Coordinates> = get_neighbors_fixed([coords[0], coords[1]], dimensions)
+                .map(Vec::from)
+                .collect();
+            assert_eq!(fixed, get_neighbors(&coords, &dimensions));
+        }
+
+        let dimensions = [3, 2, 4];
+        for index in 0..24 {
+            let coords = to_coords(index, &dimensions);
+            let array = [coords[0], coords[1], coords[2]];
+            let fixed: Vec<Coordinates> = get_neighbors_fixed(array, dimensions)
+                .map(Vec::from)
+                .collect();
+            assert_eq!(fixed, get_neighbors(&coords, &dimensions));
+        }
+        assert_eq!(get_neighbors_fixed([1, 1, 1], [3, 3, 3]).count(), 26);
+    }
+
+    #[test]
+    fn test_get_neighbor_indices_by_order_matches_coordinates() {
+        let dimensions = [3, 4, 2];
+        for index in 0..24 {
+            let coords = to_coords(index, &dimensions);
+            for order in 1..=3 {
+                let expected: Vec<usize> = get_neighbors_by_order(&coords, &dimensions, order)
+                    .iter()
+                    .map(|n| to_index(n, &dimensions))
+                    .collect();
+                assert_eq!(
+                    get_neighbor_indices_by_order(index, &dimensions, order),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_neighbors_by_order_3d_center() {
+        let dimensions = vec![3, 3, 3];
+        let center = vec![1, 1, 1];
+
+        let orthogonal = get_neighbors_by_order(&center, &dimensions, 1);
+        assert_eq!(orthogonal.len(), 6);
+        assert!(orthogonal.contains(&vec![0, 1, 1]));
+        assert!(!orthogonal.contains(&vec![0, 0, 1]));
+
+        let order_2 = get_neighbors_by_order(&center, &dimensions, 2);
+        assert_eq!(order_2.len(), 18);
+        assert!(order_2.contains(&vec![0, 0, 1]));
+        assert!(!order_2.contains(&vec![0, 0, 0]));
+
+        // Order N is the full neighborhood.
+        let mut full = get_neighbors_by_order(&center, &dimensions, 3);
+        let mut moore = get_neighbors(&center, &dimensions);
+        full.sort();
+        moore.sort();
+        assert_eq!(full, moore);
+    }
 }
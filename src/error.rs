@@ -0,0 +1,332 @@
+// src/error.rs
+
+//! The `error` module defines the errors the game engine can report.
+//!
+//! Keeping every error type in one place makes it easy to see, at a glance,
+//! everything that can go wrong, and gives front-ends a single module to
+//! import from. Each error implements `std::fmt::Display` (for messages meant
+//! for humans) and `std::error::Error` (so that it works with `?` and with
+//! error-handling crates such as `anyhow`).
+
+use std::fmt;
+
+/// Errors that can occur when creating or manipulating a `Board`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoardError {
+    /// More mines were requested than there are cells available for them.
+    TooManyMines {
+        /// How many mines were requested.
+        requested: usize,
+        /// How many cells were allowed to hold a mine.
+        available: usize,
+    },
+    /// The board would have more cells than allowed (or than a `usize` can
+    /// even count).
+    TooLarge {
+        /// The maximum number of cells that was allowed.
+        max_cells: usize,
+    },
+    /// A generator that retries random layouts until one meets a requirement
+    /// gave up without finding one.
+    GenerationFailed {
+        /// How many layouts were tried.
+        attempts: usize,
+    },
+    /// The operation only works on boards with a specific number of dimensions.
+    WrongDimensionality {
+        /// How many dimensions the operation needs.
+        expected: usize,
+        /// How many dimensions the board has.
+        actual: usize,
+    },
+    /// Coordinates have a different number of components than the board has
+    /// dimensions, e.g. `[1, 2]` on a 3D board.
+    WrongCoordinateCount {
+        /// How many components the board's coordinates have.
+        expected: usize,
+        /// How many components the given coordinates have.
+        actual: usize,
+    },
+    /// Coordinates lie beyond the edge of the board.
+    OutOfBounds {
+        /// The offending coordinates.
+        coords: Vec<usize>,
+    },
+    /// A reveal was stopped by the board's reveal limit, leaving part of the
+    /// flood-fill region hidden.
+    RevealLimitExceeded {
+        /// The limit that was reached.
+        limit: usize,
+    },
+    /// New dimensions don't describe the same number of cells as the board
+    /// has, so the board can't be reshaped into them.
+    CellCountMismatch {
+        /// How many cells the board has.
+        expected: usize,
+        /// How many cells the new dimensions describe (or `None` if the
+        /// count doesn't even fit in a `usize`).
+        actual: Option<usize>,
+    },
+    /// The number of mines on the board doesn't match the board's own
+    /// bookkeeping, e.g. because two mines were placed on the same cell.
+    MineCountMismatch {
+        /// How many mines the board thinks it has.
+        expected: usize,
+        /// How many cells actually hold a mine.
+        actual: usize,
+    },
+    /// An empty cell's stored adjacent mine count disagrees with the mines
+    /// actually around it.
+    AdjacentCountMismatch {
+        /// The coordinates of the cell.
+        coords: Vec<usize>,
+        /// How many mines really surround the cell.
+        expected: u16,
+        /// The count the cell has stored.
+        actual: u16,
+    },
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::TooManyMines {
+                requested,
+                available,
+            } => write!(
+                f,
+                "cannot place {requested} mines: only {available} cells are available"
+            ),
+            BoardError::TooLarge { max_cells } => {
+                write!(f, "the board would have more than {max_cells} cells")
+            }
+            BoardError::GenerationFailed { attempts } => write!(
+                f,
+                "no board meeting the requirements was found in {attempts} attempts"
+            ),
+            BoardError::WrongDimensionality { expected, actual } => write!(
+                f,
+                "this operation needs a {expected}-dimensional board, but the board has {actual} dimensions"
+            ),
+            BoardError::WrongCoordinateCount { expected, actual } => write!(
+                f,
+                "expected coordinates with {expected} components, found {actual}"
+            ),
+            BoardError::OutOfBounds { coords } => {
+                write!(f, "coordinates {coords:?} lie outside the board")
+            }
+            BoardError::RevealLimitExceeded { limit } => write!(
+                f,
+                "the reveal was stopped after {limit} cells by the board's reveal limit"
+            ),
+            BoardError::CellCountMismatch {
+                expected,
+                actual: Some(actual),
+            } => write!(
+                f,
+                "the new shape has {actual} cells, but the board has {expected}"
+            ),
+            BoardError::CellCountMismatch {
+                expected,
+                actual: None,
+            } => write!(
+                f,
+                "the new shape has far more cells than the board's {expected}"
+            ),
+            BoardError::MineCountMismatch { expected, actual } => write!(
+                f,
+                "the board should have {expected} mines, but {actual} cells hold one"
+            ),
+            BoardError::AdjacentCountMismatch {
+                coords,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "the cell at {coords:?} says it touches {actual} mines, but it touches {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+/// Errors that can occur when playing a `Game`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GameError {
+    /// The game has already been won or lost, so no more moves are allowed.
+    GameOver,
+    /// The move names a cell that isn't on the board. The wrapped
+    /// `BoardError` says how (see `Board::validate_coords`).
+    InvalidCoords(BoardError),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::GameOver => write!(f, "the game is already over"),
+            GameError::InvalidCoords(error) => write!(f, "invalid move: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GameError::GameOver => None,
+            GameError::InvalidCoords(error) => Some(error),
+        }
+    }
+}
+
+/// Errors that can occur when parsing text such as `"3,4,1"` into coordinates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input contained no coordinates at all.
+    Empty,
+    /// One of the comma-separated parts is not a non-negative integer.
+    InvalidToken {
+        /// The offending part, exactly as it appeared (after trimming).
+        token: String,
+        /// Which part it was, counting from zero.
+        position: usize,
+    },
+    /// One of the parts is smaller than the first valid index, e.g. a `0`
+    /// when coordinates count from 1 (see `Game::set_origin`).
+    BelowOrigin {
+        /// Which part it was, counting from zero.
+        position: usize,
+        /// The first valid index.
+        origin: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "no coordinates given"),
+            ParseError::InvalidToken { token, position } => write!(
+                f,
+                "coordinate #{} is {token:?}, which is not a non-negative integer",
+                position + 1
+            ),
+            ParseError::BelowOrigin { position, origin } => write!(
+                f,
+                "coordinate #{} is below {origin}, where counting starts",
+                position + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Errors that can occur when turning a `GameSnapshot` back into a game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The snapshot was written by a version of the format this build doesn't
+    /// know how to read (most likely a newer one).
+    UnsupportedVersion {
+        /// The version found in the snapshot.
+        version: u32,
+    },
+    /// The snapshot's contents don't describe a valid board.
+    InvalidData {
+        /// What exactly is wrong.
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::UnsupportedVersion { version } => {
+                write!(f, "unsupported snapshot version {version}")
+            }
+            SnapshotError::InvalidData { reason } => write!(f, "invalid snapshot: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_error_has_a_message() {
+        let errors: Vec<Box<dyn std::error::Error>> = vec![
+            Box::new(BoardError::TooManyMines {
+                requested: 10,
+                available: 4,
+            }),
+            Box::new(BoardError::TooLarge { max_cells: 100 }),
+            Box::new(BoardError::GenerationFailed { attempts: 5 }),
+            Box::new(BoardError::WrongDimensionality {
+                expected: 2,
+                actual: 3,
+            }),
+            Box::new(BoardError::WrongCoordinateCount {
+                expected: 2,
+                actual: 1,
+            }),
+            Box::new(BoardError::OutOfBounds { coords: vec![9, 9] }),
+            Box::new(BoardError::RevealLimitExceeded { limit: 50 }),
+            Box::new(BoardError::CellCountMismatch {
+                expected: 16,
+                actual: Some(15),
+            }),
+            Box::new(BoardError::CellCountMismatch {
+                expected: 16,
+                actual: None,
+            }),
+            Box::new(BoardError::MineCountMismatch {
+                expected: 10,
+                actual: 9,
+            }),
+            Box::new(BoardError::AdjacentCountMismatch {
+                coords: vec![1, 2],
+                expected: 3,
+                actual: 2,
+            }),
+            Box::new(GameError::GameOver),
+            Box::new(GameError::InvalidCoords(BoardError::OutOfBounds {
+                coords: vec![9, 9],
+            })),
+            Box::new(ParseError::Empty),
+            Box::new(ParseError::InvalidToken {
+                token: "x".to_string(),
+                position: 0,
+            }),
+            Box::new(ParseError::BelowOrigin {
+                position: 1,
+                origin: 1,
+            }),
+            Box::new(SnapshotError::UnsupportedVersion { version: 9 }),
+            Box::new(SnapshotError::InvalidData {
+                reason: "truncated",
+            }),
+        ];
+
+        for error in errors {
+            let message = error.to_string();
+            assert!(!message.trim().is_empty());
+            // Messages are sentence fragments meant to follow "error: ".
+            assert!(message.starts_with(char::is_lowercase), "{message}");
+        }
+    }
+
+    #[test]
+    fn test_errors_work_with_the_question_mark() {
+        fn create() -> Result<(), Box<dyn std::error::Error>> {
+            crate::board::Board::new(vec![2, 2], 5)?;
+            Ok(())
+        }
+        let error = create().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "cannot place 5 mines: only 4 cells are available"
+        );
+    }
+}
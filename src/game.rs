@@ -7,8 +7,14 @@
 //! This module will be the primary entry point for the front-end to interact
 //! with the game logic.
 
-use crate::board::Board;
-use crate::coordinates::Coordinates;
+use crate::board::{Board, RevealOutcome};
+use crate::cell::{CellKind, CellState};
+use crate::coordinates::{self, Coordinates, to_index};
+use crate::error::{BoardError, GameError, ParseError};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::time::{Duration, Instant};
 
 // The Game struct will hold the game's state.
 pub struct Game {
@@ -17,17 +23,307 @@ pub struct Game {
 
     // The current state of the game.
     state: GameState,
+
+    // How the very first reveal is protected. See `FirstClick`.
+    first_click: FirstClick,
+
+    // Whether hitting a mine ends the game. See `GameMode`.
+    mode: GameMode,
+
+    // The mines the player has hit without losing, in the order they were
+    // hit. Only ever filled in `GameMode::Practice`.
+    mines_hit: Vec<Coordinates>,
+
+    // Whether the player has already made their first reveal. First-click
+    // protection must only ever run once, before the first reveal is processed.
+    first_reveal_done: bool,
+
+    // When the clock started and stopped. The clock starts on the first reveal
+    // (not on creation), so time spent looking at an untouched board is free,
+    // and it stops as soon as the game is won or lost.
+    started_at: Option<Instant>,
+    ended_at: Option<Instant>,
+
+    // How many moves the player has made so far: reveals, chords and mark
+    // changes. A cascade counts as the one move that started it.
+    moves: usize,
+
+    // Called with the new state whenever `state` changes. See
+    // `Game::on_state_change`.
+    on_state_change: Option<StateCallback>,
+
+    // Randomness for first-click protection and `random_safe_start`. Seeded
+    // games seed it too, so that they replay identically.
+    rng: StdRng,
+
+    // The seed the game was created from, if any. See `Game::seed`.
+    seed: Option<u64>,
+
+    // Called once with the final status when the game is won or lost. See
+    // `Game::set_on_win` and `Game::set_on_loss`.
+    on_win: Option<StatusCallback>,
+    on_loss: Option<StatusCallback>,
+
+    // Whether moves are recorded in `events`. Off by default, so games
+    // nobody is listening to don't pay for the buffer. See
+    // `Game::record_events`.
+    recording_events: bool,
+
+    // Everything that happened since the last `take_events`.
+    events: Vec<Event>,
+
+    // What the first index on every axis is called when talking to the
+    // player: 0 or 1. See `Game::set_origin`.
+    origin: usize,
+}
+
+/// A callback that is told about game state changes. See
+/// `Game::on_state_change`.
+pub type StateCallback = Box<dyn FnMut(&GameState)>;
+
+/// A callback that receives the final status of a finished game. See
+/// `Game::set_on_win`.
+pub type StatusCallback = Box<dyn FnMut(&GameStatus)>;
+
+/// Cloning a game copies the board, the state and the clock, but not the
+/// registered callbacks: a clone is typically a "what if" copy for an AI to
+/// experiment on, and its moves must not set off the real game's sound
+/// effects or victory screen.
+impl Clone for Game {
+    fn clone(&self) -> Self {
+        Self {
+            board: self.board.clone(),
+            state: self.state.clone(),
+            first_click: self.first_click,
+            mode: self.mode,
+            mines_hit: self.mines_hit.clone(),
+            first_reveal_done: self.first_reveal_done,
+            started_at: self.started_at,
+            ended_at: self.ended_at,
+            moves: self.moves,
+            rng: self.rng.clone(),
+            seed: self.seed,
+            on_state_change: None,
+            on_win: None,
+            on_loss: None,
+            recording_events: self.recording_events,
+            events: self.events.clone(),
+            origin: self.origin,
+        }
+    }
 }
 
 // GameState represents the possible states of the game.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum GameState {
     /// The game is currently in progress.
     InProgress,
     /// The player has won the game.
-    Won,
+    Won {
+        /// How many moves the player needed.
+        moves: usize,
+        /// How long the game took, from the first reveal to the winning one.
+        elapsed: Option<Duration>,
+    },
     /// The player has lost the game.
-    Lost,
+    Lost {
+        /// Why the game was lost.
+        reason: LossReason,
+    },
+}
+
+/// Why a game was lost.
+///
+/// Only one way to lose exists today, but the enum is `non_exhaustive` so that
+/// new rules (such as a time limit) can add variants without breaking the
+/// `match` statements of library users.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LossReason {
+    /// The player revealed the mine at these coordinates.
+    HitMine(Coordinates),
+    /// The whole board was revealed with `Game::reveal_all_debug`.
+    Spoiled,
+}
+
+/// How the very first reveal of a game is protected from bad luck.
+///
+/// Losing on the very first click is no fun and teaches nothing, so most
+/// Minesweeper implementations quietly move mines away from the first click.
+/// The protection happens by relocating mines *before* the reveal is
+/// processed, so the player never sees the original layout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FirstClick {
+    /// No protection at all: the first click may hit a mine.
+    #[default]
+    Unprotected,
+    /// The clicked cell is guaranteed not to be a mine.
+    SafeCell,
+    /// The clicked cell *and all of its neighbors* are guaranteed to be
+    /// mine-free, so the clicked cell is a zero and the first click always
+    /// opens a region of more than one cell. If the board is too dense to
+    /// move the whole neighborhood's mines elsewhere, this falls back to
+    /// `SafeCell` behavior.
+    SafeRegion,
+}
+
+/// Whether hitting a mine ends the game.
+///
+/// Puzzle authors testing a layout, and players learning a new board shape,
+/// often want to see the whole board play out rather than start over after
+/// every mistake.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameMode {
+    /// The usual rules: the first mine revealed loses the game.
+    #[default]
+    Standard,
+    /// Revealing a mine uncovers it and records it (see `Game::mines_hit`),
+    /// but the game stays in progress. Revealing every safe cell still wins.
+    Practice,
+}
+
+/// Something that happened during play, for analytics and replays. See
+/// `Game::take_events`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A safe cell was revealed, by a click or by a cascade.
+    Revealed {
+        /// The revealed cell.
+        coords: Coordinates,
+        /// The number it showed.
+        adjacent_mines: u16,
+    },
+    /// A flag was placed.
+    Flagged {
+        /// The flagged cell.
+        coords: Coordinates,
+    },
+    /// A flag was removed.
+    Unflagged {
+        /// The cell that lost its flag.
+        coords: Coordinates,
+    },
+    /// A mine was revealed, losing the game.
+    Lost {
+        /// The mine.
+        coords: Coordinates,
+    },
+    /// A mine was revealed in `GameMode::Practice`, so the game goes on.
+    MineHit {
+        /// The mine.
+        coords: Coordinates,
+    },
+    /// The last safe cell was revealed, winning the game.
+    Won,
+}
+
+/// What a single move did, as returned by `Game::reveal`.
+///
+/// Every variant lists the cells the move uncovered, in reveal order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveResult {
+    /// The game goes on.
+    Continue {
+        /// The newly revealed cells.
+        revealed: Vec<Coordinates>,
+    },
+    /// This move won the game.
+    Won {
+        /// The newly revealed cells.
+        revealed: Vec<Coordinates>,
+    },
+    /// This move lost the game.
+    Lost {
+        /// The newly revealed cells: just the mine that was hit.
+        revealed: Vec<Coordinates>,
+    },
+}
+
+impl MoveResult {
+    /// Returns the cells the move uncovered, whatever its result.
+    pub fn revealed(&self) -> &[Coordinates] {
+        match self {
+            MoveResult::Continue { revealed }
+            | MoveResult::Won { revealed }
+            | MoveResult::Lost { revealed } => revealed,
+        }
+    }
+}
+
+/// A snapshot of the numbers a status bar typically shows.
+///
+/// Front-ends could compute each of these with separate calls, but bundling
+/// them into one value guarantees they all describe the same moment in time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameStatus {
+    /// The state of the game when the snapshot was taken.
+    pub state: GameState,
+    /// How many cells have been revealed so far.
+    pub revealed: usize,
+    /// How many cells are not mines, i.e. how many must be revealed to win.
+    pub total_safe: usize,
+    /// How many flags are currently placed.
+    pub flags: usize,
+    /// The classic "mine counter": total mines minus placed flags. This is
+    /// signed because a player can place more flags than there are mines.
+    pub mines_remaining: isize,
+    /// Time since the first reveal, or `None` if the player hasn't started yet.
+    pub elapsed: Option<Duration>,
+}
+
+impl GameStatus {
+    /// Returns how much of the board has been cleared, from `0.0` to `100.0`.
+    pub fn percent_complete(&self) -> f64 {
+        if self.total_safe == 0 {
+            return 100.0;
+        }
+        self.revealed as f64 * 100.0 / self.total_safe as f64
+    }
+}
+
+/// A read-only copy of a game that is safe to share, e.g. as a replay. See
+/// `Game::redacted_clone`.
+///
+/// It only holds what the player could see: cell states everywhere, but
+/// contents only for the cells they opened. That is deliberately not a
+/// `Game` or a `Board`, since a board has to know where every mine is, and
+/// a hidden cell stand-in would make it playable with the wrong answers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RedactedGame {
+    /// The dimensions of the board.
+    pub dimensions: Vec<usize>,
+    /// Every cell's state, in flat index order.
+    pub states: Vec<CellState>,
+    /// What every revealed cell turned out to be, in flat index order, or
+    /// `None` for cells the player never opened (flagged ones included).
+    pub contents: Vec<Option<CellKind>>,
+    /// The status bar numbers at the time of the copy.
+    pub status: GameStatus,
+    /// How many moves the player had made. See `Game::move_count`.
+    pub moves: usize,
+}
+
+impl RedactedGame {
+    /// Returns what the cell at `coords` turned out to be, or `None` if the
+    /// player never opened it or the coordinates are not on the board.
+    pub fn contents_at(&self, coords: &Coordinates) -> Option<&CellKind> {
+        let index = self.index_of(coords)?;
+        self.contents[index].as_ref()
+    }
+
+    /// Returns the state of the cell at `coords`, or `None` if the
+    /// coordinates are not on the board.
+    pub fn state_at(&self, coords: &Coordinates) -> Option<&CellState> {
+        let index = self.index_of(coords)?;
+        Some(&self.states[index])
+    }
+
+    fn index_of(&self, coords: &Coordinates) -> Option<usize> {
+        let on_board = coords.len() == self.dimensions.len()
+            && coords.iter().zip(&self.dimensions).all(|(c, d)| c < d);
+        on_board.then(|| to_index(coords, &self.dimensions))
+    }
 }
 
 impl Game {
@@ -37,11 +333,156 @@ impl Game {
     ///
     /// * `dimensions` - A vector defining the size of each dimension of the board.
     /// * `num_mines` - The number of mines to place on the board.
-    pub fn new(dimensions: Vec<usize>, num_mines: usize) -> Self {
-        let board = Board::new(dimensions, num_mines);
+    ///
+    /// # Errors
+    ///
+    /// Returns the `BoardError` from `Board::new` if the board can't be
+    /// created (too large, or too many mines).
+    pub fn new(dimensions: Vec<usize>, num_mines: usize) -> Result<Self, BoardError> {
+        Ok(Self::from_board(Board::new(dimensions, num_mines)?))
+    }
+
+    /// Creates a new game whose board, and every random choice made during
+    /// play (such as where first-click protection moves mines), is decided
+    /// by `seed`. The same seed and the same moves give the same game.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Game::new`.
+    pub fn new_seeded(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        let board = Board::new_seeded(dimensions, num_mines, seed)?;
+        let mut game = Self::from_board(board);
+        game.use_seed(seed);
+        Ok(game)
+    }
+
+    /// Creates the "daily puzzle" for a given date.
+    ///
+    /// Everyone who plays on the same day, with the same dimensions and mine
+    /// count, gets exactly the same board, so scores can be compared.
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - A vector defining the size of each dimension of the board.
+    /// * `num_mines` - The number of mines to place on the board.
+    /// * `date` - The date as `(year, month, day)`.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Game::new`.
+    pub fn daily(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        date: (u16, u8, u8),
+    ) -> Result<Self, BoardError> {
+        Self::new_seeded(dimensions, num_mines, date_seed(date))
+    }
+
+    /// Creates a new game that starts with an opening already revealed.
+    ///
+    /// This is meant for teaching: instead of facing a blank board, the
+    /// learner starts with a randomly chosen zero cell (and the whole region
+    /// it cascades into) uncovered. The opening doesn't count as a move and
+    /// doesn't start the clock. If the board has no zero cell at all, the
+    /// game simply starts untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - A vector defining the size of each dimension of the board.
+    /// * `num_mines` - The number of mines to place on the board.
+    /// * `seed` - Seed for the board layout and the choice of opening.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Game::new`.
+    pub fn new_with_auto_open(
+        dimensions: Vec<usize>,
+        num_mines: usize,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        let mut board = Board::new_seeded(dimensions, num_mines, seed)?;
+        let zero_cells: Vec<usize> = (0..board.cell_count())
+            .filter(|&i| board.is_zero_at(i))
+            .collect();
+        if let Some(&index) = zero_cells.choose(&mut StdRng::seed_from_u64(seed)) {
+            let coords = board.coords_of(index);
+            board.reveal(&coords);
+        }
+        // `resume` notices the revealed cells and skips first-click
+        // protection, which would otherwise move mines under the opening.
+        let mut game = Self::resume(board, 0, GameMode::Standard);
+        game.use_seed(seed);
+        Ok(game)
+    }
+
+    /// Seeds the game's random number generator, and remembers the seed for
+    /// `seed`.
+    fn use_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+        self.seed = Some(seed);
+    }
+
+    /// Rebuilds a game in progress (or finished) from a saved board.
+    ///
+    /// The game state is derived from the board itself: a revealed mine means
+    /// a standard game was lost, and all safe cells revealed means it was
+    /// won. In `GameMode::Practice`, revealed mines are the mines hit so far
+    /// (in flat index order, since saves don't keep the order they were hit
+    /// in). Saves don't carry the clock, so the elapsed time restarts from
+    /// the first reveal after loading.
+    pub(crate) fn resume(board: Board, moves: usize, mode: GameMode) -> Self {
+        let first_reveal_done = board.revealed_count() > 0;
+        let mut hit_mines: Vec<Coordinates> = (0..board.cell_count())
+            .filter(|&i| board.mines.get(i) && board.states[i] == CellState::Revealed)
+            .map(|index| board.coords_of(index))
+            .collect();
+
+        let mut game = Self::from_board(board);
+        game.first_reveal_done = first_reveal_done;
+        game.moves = moves;
+        game.mode = mode;
+        if mode == GameMode::Practice {
+            game.mines_hit = hit_mines;
+        } else if !hit_mines.is_empty() {
+            game.state = GameState::Lost {
+                reason: LossReason::HitMine(hit_mines.remove(0)),
+            };
+        }
+        if game.state == GameState::InProgress && game.is_won() {
+            game.state = GameState::Won {
+                moves,
+                elapsed: None,
+            };
+        }
+        game
+    }
+
+    /// Creates a new game around an existing board.
+    ///
+    /// This is how hand-built layouts (see `Board::from_mines`) are played.
+    pub fn from_board(board: Board) -> Self {
         Self {
             board,
             state: GameState::InProgress,
+            first_click: FirstClick::default(),
+            mode: GameMode::default(),
+            mines_hit: Vec::new(),
+            first_reveal_done: false,
+            started_at: None,
+            ended_at: None,
+            moves: 0,
+            rng: StdRng::from_entropy(),
+            seed: None,
+            on_state_change: None,
+            on_win: None,
+            on_loss: None,
+            recording_events: false,
+            events: Vec::new(),
+            origin: 0,
         }
     }
 
@@ -50,30 +491,1480 @@ impl Game {
         &self.state
     }
 
+    /// Returns a read-only view of the game board.
+    ///
+    /// Front-ends need to look at the cells to draw them, but all changes
+    /// must go through `Game` so that the game state stays consistent.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns the seed the game was created from, so players can share it,
+    /// or `None` for a game that wasn't seeded.
+    ///
+    /// Passing the seed back to the constructor that made the game (with the
+    /// same dimensions and mine count) recreates it: `new_seeded` for seeded
+    /// games and daily puzzles, `new_with_auto_open` for games with an
+    /// opening.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Returns a read-only copy of the game that is safe to share, e.g. as a
+    /// replay, without giving away the cells the player never opened.
+    ///
+    /// Every cell that isn't revealed, flagged and question-marked ones
+    /// included, keeps its state but not its contents, so hidden mines look
+    /// exactly like hidden safe cells. Revealed cells, the status bar and the
+    /// move count are kept. The seed is left out too, since it would let
+    /// someone rebuild the layout. See `RedactedGame`.
+    pub fn redacted_clone(&self) -> RedactedGame {
+        let board = &self.board;
+        RedactedGame {
+            dimensions: board.dimensions().to_vec(),
+            states: board.states.clone(),
+            contents: (0..board.cell_count())
+                .map(|i| (board.states[i] == CellState::Revealed).then(|| board.kind_at(i)))
+                .collect(),
+            status: self.status(),
+            moves: self.moves,
+        }
+    }
+
+    /// Returns how many moves the player has made, for scoring and stats.
+    ///
+    /// Every reveal, chord and flag or mark change counts as one move, no
+    /// matter how many cells it affects: a flood fill that opens a hundred
+    /// cells is still a single move. Moves that don't change anything don't
+    /// count: revealing a revealed or flagged cell, a chord whose flags don't
+    /// add up, or flagging a revealed cell.
+    pub fn move_count(&self) -> usize {
+        self.moves
+    }
+
+    /// Returns the time elapsed since the first reveal.
+    ///
+    /// Returns `None` if the player hasn't revealed anything yet. Once the game
+    /// is over, the returned duration stops growing.
+    pub fn elapsed(&self) -> Option<Duration> {
+        let start = self.started_at?;
+        Some(match self.ended_at {
+            Some(end) => end.duration_since(start),
+            None => start.elapsed(),
+        })
+    }
+
+    /// Returns a snapshot of the common status-bar readouts. See `GameStatus`.
+    pub fn status(&self) -> GameStatus {
+        let flags = self.board.flag_count();
+        GameStatus {
+            state: self.state.clone(),
+            revealed: self.board.revealed_count(),
+            total_safe: self.board.safe_cells(),
+            flags,
+            mines_remaining: self.board.num_mines() as isize - flags as isize,
+            elapsed: self.elapsed(),
+        }
+    }
+
+    /// Registers a callback that is called with the new state every time the
+    /// game state changes, i.e. when the game is won or lost.
+    ///
+    /// This lets front-ends trigger sound effects or animations without
+    /// polling `state()` after every move. Only one callback can be
+    /// registered; registering another replaces it. When no callback is
+    /// registered, state changes cost nothing extra.
+    pub fn on_state_change(&mut self, f: StateCallback) {
+        self.on_state_change = Some(f);
+    }
+
+    /// Registers a callback that is called once, with the final status, when
+    /// the game is won.
+    ///
+    /// Unlike `on_state_change`, the callback gets the whole `GameStatus`,
+    /// so a victory screen or a high-score table has everything it needs in
+    /// one place. Registering another callback replaces this one.
+    pub fn set_on_win(&mut self, f: impl FnMut(&GameStatus) + 'static) {
+        self.on_win = Some(Box::new(f));
+    }
+
+    /// Registers a callback that is called once, with the final status, when
+    /// the game is lost. See `set_on_win`.
+    pub fn set_on_loss(&mut self, f: impl FnMut(&GameStatus) + 'static) {
+        self.on_loss = Some(Box::new(f));
+    }
+
+    /// Sets how the first reveal is protected. See `FirstClick`.
+    ///
+    /// This only has an effect if it is called before the first reveal.
+    pub fn set_first_click(&mut self, policy: FirstClick) {
+        self.first_click = policy;
+    }
+
+    /// Returns how the first reveal is protected.
+    pub fn first_click(&self) -> FirstClick {
+        self.first_click
+    }
+
+    /// Sets whether hitting a mine ends the game. See `GameMode`.
+    ///
+    /// It can be switched at any point while the game is in progress, but
+    /// a game already lost stays lost.
+    pub fn set_mode(&mut self, mode: GameMode) {
+        self.mode = mode;
+    }
+
+    /// Returns whether hitting a mine ends the game.
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    /// Returns the mines hit in `GameMode::Practice`, in the order they were
+    /// hit. In a standard game, the mine that lost it is in `LossReason`
+    /// instead, so this is always empty.
+    pub fn mines_hit(&self) -> &[Coordinates] {
+        &self.mines_hit
+    }
+
+    /// Sets whether the player counts coordinates from 0 (the default) or
+    /// from 1, e.g. to match the labels drawn next to the grid.
+    ///
+    /// The origin only applies where coordinates cross the boundary to the
+    /// player: `parse_coords`, `format_coords` and `validate_coords`. Every
+    /// other method, like the board itself, always counts from 0, so convert
+    /// with `parse_coords` before calling them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `origin` is neither 0 nor 1.
+    pub fn set_origin(&mut self, origin: usize) {
+        assert!(origin <= 1, "the origin must be 0 or 1, not {origin}");
+        self.origin = origin;
+    }
+
+    /// Returns the index the player counts coordinates from: 0 or 1.
+    pub fn origin(&self) -> usize {
+        self.origin
+    }
+
+    /// Parses coordinates typed by the player (see `coordinates::parse`)
+    /// and converts them from the player's origin to the 0-based ones
+    /// everything else uses.
+    ///
+    /// This doesn't check the coordinates against the board; pass the
+    /// result to `Board::validate_coords` for that, or check the player's
+    /// input with `validate_coords` first.
+    ///
+    /// # Errors
+    ///
+    /// * Any error from `coordinates::parse`.
+    /// * `ParseError::BelowOrigin` for a `0` when counting from 1.
+    pub fn parse_coords(&self, s: &str) -> Result<Coordinates, ParseError> {
+        coordinates::parse(s)?
+            .into_iter()
+            .enumerate()
+            .map(|(position, c)| {
+                c.checked_sub(self.origin).ok_or(ParseError::BelowOrigin {
+                    position,
+                    origin: self.origin,
+                })
+            })
+            .collect()
+    }
+
+    /// Formats 0-based coordinates for the player, counting from the
+    /// game's origin. The result can be read back with `parse_coords`.
+    pub fn format_coords(&self, coords: &Coordinates) -> String {
+        let shown: Coordinates = coords.iter().map(|&c| c + self.origin).collect();
+        coordinates::format(&shown)
+    }
+
+    /// Checks coordinates given in the player's convention (see
+    /// `set_origin`) against the board.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Board::validate_coords`, except that coordinates below
+    /// the origin are out of bounds too. Errors quote the coordinates as the
+    /// player gave them.
+    pub fn validate_coords(&self, coords: &Coordinates) -> Result<(), BoardError> {
+        let out_of_bounds = || BoardError::OutOfBounds {
+            coords: coords.clone(),
+        };
+        let internal = coords
+            .iter()
+            .map(|&c| c.checked_sub(self.origin))
+            .collect::<Option<Coordinates>>()
+            .ok_or_else(out_of_bounds)?;
+        self.board
+            .validate_coords(&internal)
+            .map_err(|error| match error {
+                BoardError::OutOfBounds { .. } => out_of_bounds(),
+                other => other,
+            })
+    }
+
+    /// Returns `true` while the player is still allowed to change the board.
+    ///
+    /// `Game` is the layer that enforces the rules of play, so every method
+    /// that modifies the board checks this first. `Board` deliberately does
+    /// not know about game state, so that editors, solvers and tests can
+    /// manipulate it freely.
+    pub fn can_modify(&self) -> bool {
+        self.state == GameState::InProgress
+    }
+
     /// Toggles a flag on a cell.
-    pub fn toggle_flag(&mut self, coords: &Coordinates) {
-        if self.state == GameState::InProgress {
-            self.board.toggle_flag(coords);
+    ///
+    /// # Returns
+    ///
+    /// The cell's new state, or `None` if the coordinates are not on the
+    /// board. See `Board::toggle_flag`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::GameOver` (and leaves the board untouched) once
+    /// the game is over.
+    pub fn toggle_flag(&mut self, coords: &Coordinates) -> Result<Option<CellState>, GameError> {
+        self.ensure_in_progress()?;
+        let before = self.board.cell_state(coords);
+        let after = self.board.toggle_flag(coords);
+        self.record_mark_change(coords, before);
+        Ok(after)
+    }
+
+    /// Moves a cell to its next mark (flag, question mark, or none). See
+    /// `Board::cycle_mark`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::GameOver` (and leaves the board untouched) once
+    /// the game is over.
+    pub fn cycle_mark(&mut self, coords: &Coordinates) -> Result<(), GameError> {
+        self.ensure_in_progress()?;
+        let before = self.board.cell_state(coords);
+        self.board.cycle_mark(coords);
+        self.record_mark_change(coords, before);
+        Ok(())
+    }
+
+    /// Turns event recording on or off. It is off by default.
+    ///
+    /// A big cascade records one event per opened cell, and the events pile
+    /// up until `take_events` collects them, so only games whose events
+    /// someone reads (a replay recorder, an analytics hook) should record
+    /// them. Turning recording off drops the events not collected yet.
+    pub fn record_events(&mut self, on: bool) {
+        self.recording_events = on;
+        if !on {
+            self.events = Vec::new();
+        }
+    }
+
+    /// Returns whether events are being recorded. See `record_events`.
+    pub fn records_events(&self) -> bool {
+        self.recording_events
+    }
+
+    /// Returns everything that happened since the last call, oldest first,
+    /// and clears the list. Nothing is recorded unless `record_events` has
+    /// turned recording on.
+    ///
+    /// Every revealed safe cell (including each cell of a cascade) gets its
+    /// own `Event::Revealed`, flags placed and removed are recorded, every
+    /// mine hit in `GameMode::Practice` adds `Event::MineHit`, and the move
+    /// that ends the game adds `Event::Lost` or `Event::Won`.
+    pub fn take_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Counts a move if the cell at `coords` changed since it was in state
+    /// `before`, and records `Event::Flagged` or `Event::Unflagged` if it
+    /// gained or lost a flag.
+    fn record_mark_change(&mut self, coords: &Coordinates, before: Option<CellState>) {
+        if self.board.cell_state(coords) != before {
+            self.moves += 1;
+        }
+        let was_flagged = before == Some(CellState::Flagged);
+        let is_flagged = self.board.is_flagged(coords);
+        let coords = coords.clone();
+        match (was_flagged, is_flagged) {
+            (false, true) => self.push_event(Event::Flagged { coords }),
+            (true, false) => self.push_event(Event::Unflagged { coords }),
+            _ => {}
+        }
+    }
+
+    /// Adds `event` to the buffer, if events are being recorded.
+    fn push_event(&mut self, event: Event) {
+        if self.recording_events {
+            self.events.push(event);
+        }
+    }
+
+    /// Records an `Event::Revealed` for every safe cell in `revealed`.
+    fn record_revealed(&mut self, revealed: &[Coordinates]) {
+        if !self.recording_events {
+            return;
+        }
+        for coords in revealed {
+            let index = self.board.index_of(coords);
+            if let CellKind::Empty { adjacent_mines } = self.board.kind_at(index) {
+                self.push_event(Event::Revealed {
+                    coords: coords.clone(),
+                    adjacent_mines,
+                });
+            }
         }
     }
 
     /// Reveals a cell.
-    pub fn reveal(&mut self, coords: &Coordinates) {
+    ///
+    /// # Returns
+    ///
+    /// A `MoveResult` telling whether the game goes on, was won or was lost,
+    /// together with every cell the reveal uncovered, so a front-end can
+    /// update (and animate) exactly those cells.
+    ///
+    /// # Errors
+    ///
+    /// * `GameError::GameOver` (and the board is left untouched) once the
+    ///   game is over.
+    /// * `GameError::InvalidCoords` if `coords` is not a cell on the board.
+    pub fn reveal(&mut self, coords: &Coordinates) -> Result<MoveResult, GameError> {
+        self.ensure_in_progress()?;
+        self.ensure_on_board(coords)?;
+        // Clicking a flagged, frozen or revealed cell opens nothing, so it
+        // is no first click: it must neither use up first-click protection
+        // nor start the clock.
+        let opens = self.board.cell_state(coords).is_some_and(|s| s.is_hidden())
+            && !self.board.is_frozen(coords);
+        if opens {
+            if !self.first_reveal_done {
+                self.first_reveal_done = true;
+                self.protect_first_click(coords);
+            }
+            // Not part of the block above: a resumed or auto-opened game has
+            // already had its first reveal, but its clock hasn't started yet.
+            self.started_at.get_or_insert_with(Instant::now);
+        }
+
+        let outcome = self.board.reveal_many(std::slice::from_ref(coords));
+        Ok(self.finish_reveal(outcome))
+    }
+
+    /// Reveals a randomly chosen safe cell, e.g. to kick off an auto-play
+    /// demo.
+    ///
+    /// The cell is drawn from the hidden cells that are not mines (the game
+    /// knows where they are), using the game's random number generator, so
+    /// a seeded game (see `new_seeded`) always starts the same way. As with
+    /// any reveal, first-click protection still applies, so with
+    /// `FirstClick::SafeRegion` the opening is at least a small region.
+    ///
+    /// # Returns
+    ///
+    /// The `MoveResult` of the reveal. If every safe hidden cell carries a
+    /// flag, nothing is revealed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::GameOver` (and leaves the board untouched) once
+    /// the game is over.
+    pub fn random_safe_start(&mut self) -> Result<MoveResult, GameError> {
+        self.ensure_in_progress()?;
+        let candidates: Vec<usize> = (0..self.board.cell_count())
+            .filter(|&i| !self.board.mines.get(i) && self.board.states[i].is_hidden())
+            .filter(|&i| !self.board.is_frozen(&self.board.coords_of(i)))
+            .collect();
+        let Some(&index) = candidates.choose(&mut self.rng) else {
+            return Ok(MoveResult::Continue {
+                revealed: Vec::new(),
+            });
+        };
+        let coords = self.board.coords_of(index);
+        self.reveal(&coords)
+    }
+
+    /// Plays a reveal on a copy of the game, leaving this one untouched.
+    ///
+    /// This is the building block for lookahead: an AI can try a move, look
+    /// at the resulting game and result, and throw both away. The copy has
+    /// no callbacks (see the `Clone` impl). Note that if this would be the
+    /// first reveal, first-click protection may move mines in the copy only.
+    ///
+    /// # Errors
+    ///
+    /// The same as `reveal`.
+    pub fn simulate_reveal(&self, coords: &Coordinates) -> Result<(Game, MoveResult), GameError> {
+        let mut copy = self.clone();
+        let result = copy.reveal(coords)?;
+        Ok((copy, result))
+    }
+
+    /// Chords on a revealed number, revealing its unflagged neighbors if its
+    /// flags add up. See `Board::chord`.
+    ///
+    /// # Returns
+    ///
+    /// A `MoveResult` just like `reveal`'s. If the chord didn't apply, it is
+    /// `MoveResult::Continue` with no revealed cells.
+    ///
+    /// # Errors
+    ///
+    /// * `GameError::GameOver` (and the board is left untouched) once the
+    ///   game is over.
+    /// * `GameError::InvalidCoords` if `coords` is not a cell on the board.
+    pub fn chord(&mut self, coords: &Coordinates) -> Result<MoveResult, GameError> {
+        self.ensure_in_progress()?;
+        self.ensure_on_board(coords)?;
+        // A chord needs a revealed number, so the first reveal (and with it
+        // first-click protection and the clock) has always happened already.
+        let outcome = self.board.chord(coords);
+        Ok(self.finish_reveal(outcome))
+    }
+
+    /// Reveals a hidden cell, or chords on a revealed one: the "click
+    /// anything" move of dual-click play styles.
+    ///
+    /// Clicking a revealed number whose flags add up opens its other
+    /// neighbors (see `chord`); clicking any other cell behaves exactly like
+    /// `reveal`.
+    ///
+    /// # Errors
+    ///
+    /// The same as `reveal` and `chord`.
+    pub fn reveal_smart(&mut self, coords: &Coordinates) -> Result<MoveResult, GameError> {
+        if self.board.is_revealed(coords) {
+            self.chord(coords)
+        } else {
+            self.reveal(coords)
+        }
+    }
+
+    /// Counts the move, updates the game state after a reveal on the board,
+    /// and turns its outcome into the `MoveResult` the player sees.
+    ///
+    /// A reveal that did nothing (a revealed or flagged cell, or a chord
+    /// whose flags don't add up) isn't a move.
+    fn finish_reveal(&mut self, outcome: RevealOutcome) -> MoveResult {
+        if !matches!(outcome, RevealOutcome::NoOp) {
+            self.moves += 1;
+        }
+        let (revealed, hit_mine) = match outcome {
+            RevealOutcome::Safe { revealed, .. } => (revealed, None),
+            RevealOutcome::NoOp => (Vec::new(), None),
+            // A mine is never part of a cascade, so it is the only cell
+            // this reveal uncovered.
+            RevealOutcome::HitMine(mine) => (vec![mine.clone()], Some(mine)),
+        };
+        self.record_revealed(&revealed);
+        self.update_state_after_reveal(hit_mine.into_iter().collect());
+
+        match self.state {
+            GameState::InProgress => MoveResult::Continue { revealed },
+            GameState::Won { .. } => MoveResult::Won { revealed },
+            GameState::Lost { .. } => MoveResult::Lost { revealed },
+        }
+    }
+
+    /// Reveals every hidden cell that doesn't carry a flag.
+    ///
+    /// See `Board::reveal_all_unflagged`. If any flag was wrong, a mine is
+    /// revealed and the game is lost; otherwise the game is won.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::GameOver` (and leaves the board untouched) once
+    /// the game is over.
+    pub fn reveal_all_unflagged(&mut self) -> Result<(), GameError> {
+        self.ensure_in_progress()?;
+        // There's no single clicked cell to protect here, so first-click
+        // protection doesn't apply, but the clock still has to start.
+        self.first_reveal_done = true;
+        self.started_at.get_or_insert_with(Instant::now);
+
+        let targets: Vec<Coordinates> = (0..self.board.cell_count())
+            .filter(|&i| self.board.states[i].is_hidden())
+            .map(|i| self.board.coords_of(i))
+            .filter(|coords| !self.board.is_frozen(coords))
+            .collect();
+        // The board only reports the first mine it uncovers, but a practice
+        // game records every one of them.
+        let mines: Vec<Coordinates> = targets
+            .iter()
+            .filter(|coords| self.board.mines.get(self.board.index_of(coords)))
+            .cloned()
+            .collect();
+        if let RevealOutcome::NoOp = self.board.reveal_all_unflagged() {
+            return Ok(());
+        }
+        self.moves += 1;
+        self.record_revealed(&targets);
+        self.update_state_after_reveal(mines);
+        Ok(())
+    }
+
+    /// Reveals the whole board, mines included, for debugging or as a
+    /// spoiler. See `Board::reveal_all`.
+    ///
+    /// Uncovering every cell would otherwise look like a win to the next
+    /// move, so the game ends right here as lost, with
+    /// `LossReason::Spoiled`. It isn't counted as a move and records no
+    /// events, but the loss callbacks do fire.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::GameOver` (and leaves the board untouched) once
+    /// the game is over.
+    pub fn reveal_all_debug(&mut self) -> Result<(), GameError> {
+        self.ensure_in_progress()?;
+        self.board.reveal_all();
+        self.ended_at = Some(Instant::now());
+        self.set_state(GameState::Lost {
+            reason: LossReason::Spoiled,
+        });
+        Ok(())
+    }
+
+    /// Returns `GameError::GameOver` unless the player may still change the
+    /// board (see `can_modify`).
+    ///
+    /// Silently ignoring moves on a finished game would hide bugs in
+    /// front-ends, so every move reports it instead.
+    fn ensure_in_progress(&self) -> Result<(), GameError> {
+        if self.can_modify() {
+            Ok(())
+        } else {
+            Err(GameError::GameOver)
+        }
+    }
+
+    /// Returns `GameError::InvalidCoords` unless `coords` names a cell on
+    /// the board.
+    ///
+    /// The board only checks coordinates in debug builds on its fast paths,
+    /// and in release builds coordinates with the wrong number of components
+    /// quietly land on the wrong cell. Moves come straight from players (and
+    /// front-ends), so `Game` checks them at the door.
+    fn ensure_on_board(&self, coords: &Coordinates) -> Result<(), GameError> {
+        self.board
+            .validate_coords(coords)
+            .map_err(GameError::InvalidCoords)
+    }
+
+    /// Moves the game to `Lost` or `Won` if the last reveal ended it.
+    ///
+    /// In `GameMode::Practice`, mines are only recorded, and the game goes
+    /// on (and may even be won by the same move).
+    ///
+    /// # Arguments
+    ///
+    /// * `hit_mines` - Every mine the reveal uncovered, in reveal order. A
+    ///   standard game is lost to the first one.
+    fn update_state_after_reveal(&mut self, hit_mines: Vec<Coordinates>) {
+        // The clock must be stopped *before* building the final state, so
+        // that `Won` records the same elapsed time `elapsed()` reports.
+        for coords in hit_mines {
+            if self.mode == GameMode::Standard {
+                self.ended_at = Some(Instant::now());
+                self.push_event(Event::Lost {
+                    coords: coords.clone(),
+                });
+                self.set_state(GameState::Lost {
+                    reason: LossReason::HitMine(coords),
+                });
+                return;
+            }
+            self.push_event(Event::MineHit {
+                coords: coords.clone(),
+            });
+            self.mines_hit.push(coords);
+        }
+        if self.is_won() {
+            self.ended_at = Some(Instant::now());
+            self.flag_remaining_mines();
+            self.push_event(Event::Won);
+            self.set_state(GameState::Won {
+                moves: self.moves,
+                elapsed: self.elapsed(),
+            });
+        }
+    }
+
+    /// Flags every mine that isn't flagged yet, as is customary once the game
+    /// is won: every cell still covered at that point must be a mine. Each
+    /// new flag is recorded as an `Event::Flagged`, so front-ends can draw it
+    /// like any other.
+    fn flag_remaining_mines(&mut self) {
+        for index in 0..self.board.cell_count() {
+            if self.board.mines.get(index) && self.board.states[index].is_hidden() {
+                self.board.states[index] = CellState::Flagged;
+                let coords = self.board.coords_of(index);
+                self.push_event(Event::Flagged { coords });
+            }
+        }
+    }
+
+    /// Changes the game state and notifies the `on_state_change` callback,
+    /// as well as the win or loss callback if the game just ended.
+    fn set_state(&mut self, state: GameState) {
+        self.state = state;
+        if let Some(callback) = &mut self.on_state_change {
+            callback(&self.state);
+        }
+
         if self.state == GameState::InProgress {
-            if self.board.reveal(coords) {
-                self.state = GameState::Lost;
-            } else if self.is_won() {
-                self.state = GameState::Won;
+            return;
+        }
+        let status = self.status();
+        let callback = match self.state {
+            GameState::Won { .. } => &mut self.on_win,
+            _ => &mut self.on_loss,
+        };
+        if let Some(callback) = callback {
+            callback(&status);
+        }
+    }
+
+    /// Relocates mines according to the first-click policy.
+    fn protect_first_click(&mut self, coords: &Coordinates) {
+        match self.first_click {
+            FirstClick::Unprotected => {}
+            FirstClick::SafeCell => {
+                let protected = std::slice::from_ref(coords);
+                self.board.relocate_mines_with_rng(protected, &mut self.rng);
+            }
+            FirstClick::SafeRegion => {
+                let mut region = self.board.neighbors(coords);
+                region.push(coords.clone());
+
+                // `relocate_mines` leaves the board untouched when it can't
+                // find enough room, so falling back is just a second, smaller
+                // request. The single cell can only fail on a board that is
+                // entirely mines, where no protection is possible anyway.
+                if !self.board.relocate_mines_with_rng(&region, &mut self.rng) {
+                    let protected = std::slice::from_ref(coords);
+                    self.board.relocate_mines_with_rng(protected, &mut self.rng);
+                }
             }
         }
     }
 
     /// Checks if the game has been won.
+    ///
+    /// The game is won when every cell that is *not* a mine has been revealed.
+    /// Mine cells are deliberately ignored: whether they are hidden, flagged or
+    /// even revealed (e.g. when showing the board after a loss) has no bearing
+    /// on whether all the safe cells were found.
     fn is_won(&self) -> bool {
-        // The game is won if all non-mine cells are revealed.
-        self.board
-            .cells
-            .iter()
-            .all(|cell| (cell.kind != crate::cell::CellKind::Mine) == (cell.state == crate::cell::CellState::Revealed))
+        let board = &self.board;
+        let revealed_safe = (0..board.cell_count())
+            .filter(|&i| !board.mines.get(i) && board.states[i] == CellState::Revealed)
+            .count();
+        revealed_safe == self.board.safe_cells()
+    }
+}
+
+/// Turns a `(year, month, day)` date into a well-mixed 64-bit seed.
+///
+/// We can't use `std::hash::DefaultHasher` here: its output is allowed to
+/// change between Rust releases, which would silently give players on
+/// different builds different daily puzzles. Instead the date is packed into
+/// a number (e.g. `20240131`) and scrambled with the SplitMix64 finalizer, a
+/// tiny, fixed function that spreads neighboring dates far apart.
+fn date_seed((year, month, day): (u16, u8, u8)) -> u64 {
+    let mut z = year as u64 * 10_000 + month as u64 * 100 + day as u64;
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::{to_coords, to_index};
+
+    #[test]
+    fn test_safe_region_first_click_opens_region() {
+        // Repeat a few times, since mine placement is random.
+        for _ in 0..20 {
+            let mut game = Game::new(vec![10, 10], 10).unwrap();
+            game.set_first_click(FirstClick::SafeRegion);
+
+            let coords = vec![5, 5];
+            game.reveal(&coords).unwrap();
+
+            let cell = game
+                .board()
+                .cell_at(to_index(&coords, game.board().dimensions()));
+            assert_eq!(cell.kind, CellKind::Empty { adjacent_mines: 0 });
+            assert_eq!(cell.state, CellState::Revealed);
+            assert_eq!(*game.state(), GameState::InProgress);
+        }
+    }
+
+    #[test]
+    fn test_safe_region_first_click_always_opens_more_than_one_cell() {
+        // Corners and edges have smaller neighborhoods, so try those too.
+        for coords in [vec![0, 0], vec![9, 0], vec![4, 9], vec![5, 5]] {
+            for _ in 0..10 {
+                let mut game = Game::new(vec![10, 10], 20).unwrap();
+                game.set_first_click(FirstClick::SafeRegion);
+                game.reveal(&coords).unwrap();
+                assert!(game.board().revealed_count() > 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reveal_that_opens_nothing_keeps_first_click_protection() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        game.set_first_click(FirstClick::SafeCell);
+
+        // Clicking a flagged or a frozen cell is no first click.
+        game.toggle_flag(&vec![0, 0]).unwrap();
+        game.board.set_frozen(&vec![1, 1], true).unwrap();
+        for coords in [vec![0, 0], vec![1, 1]] {
+            assert_eq!(
+                game.reveal(&coords),
+                Ok(MoveResult::Continue {
+                    revealed: Vec::new()
+                })
+            );
+        }
+        assert!(!game.first_reveal_done);
+        assert_eq!(game.elapsed(), None);
+
+        // So the real first click, on the mine, is still protected.
+        game.toggle_flag(&vec![0, 0]).unwrap();
+        game.reveal(&vec![0, 0]).unwrap();
+        assert_eq!(*game.state(), GameState::InProgress);
+        assert!(game.board().is_revealed(&vec![0, 0]));
+        assert!(game.elapsed().is_some());
+    }
+
+    #[test]
+    fn test_status_on_partially_played_board() {
+        // Mine at the top-left corner of a 3x3 board.
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+
+        let before = game.status();
+        assert_eq!(before.elapsed, None);
+        assert_eq!(before.revealed, 0);
+
+        // (1,1) touches the mine, so only that one cell is revealed.
+        game.reveal(&vec![1, 1]).unwrap();
+        game.toggle_flag(&vec![0, 0]).unwrap();
+        game.toggle_flag(&vec![2, 2]).unwrap();
+
+        let status = game.status();
+        assert_eq!(status.state, GameState::InProgress);
+        assert_eq!(status.revealed, 1);
+        assert_eq!(status.total_safe, 8);
+        assert_eq!(status.flags, 2);
+        assert_eq!(status.mines_remaining, -1);
+        assert!(status.elapsed.is_some());
+        assert_eq!(status.percent_complete(), 12.5);
+    }
+
+    #[test]
+    fn test_toggle_flag_is_noop_after_loss() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+
+        game.reveal(&vec![0, 0]).unwrap();
+        assert!(matches!(game.state(), GameState::Lost { .. }));
+        assert!(!game.can_modify());
+
+        assert_eq!(game.toggle_flag(&vec![2, 2]), Err(GameError::GameOver));
+        assert_eq!(game.board().cell_at(8).state, CellState::Hidden);
+        assert_eq!(game.board().flag_count(), 0);
+    }
+
+    #[test]
+    fn test_revealing_all_safe_cells_wins() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+
+        // (2,2) is a zero cell, so it cascades over every safe cell.
+        game.reveal(&vec![2, 2]).unwrap();
+        assert!(matches!(game.state(), GameState::Won { .. }));
+        // The mine is never revealed; winning flags it instead.
+        assert_eq!(game.board().cell_at(0).state, CellState::Flagged);
+    }
+
+    #[test]
+    fn test_is_won_ignores_revealed_mines() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+
+        // Revealing only the mine, directly on the board (so no loss
+        // transition happens), must not count as a win.
+        game.board.reveal(&vec![0, 0]);
+        assert!(!game.is_won());
+
+        // Once every safe cell is revealed too, the revealed mine must not
+        // stop the win check from succeeding.
+        game.board.reveal(&vec![2, 2]);
+        assert!(game.is_won());
+    }
+
+    #[test]
+    fn test_loss_records_the_mine_that_was_hit() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+
+        game.reveal(&vec![0, 0]).unwrap();
+        match game.state() {
+            GameState::Lost {
+                reason: LossReason::HitMine(coords),
+            } => assert_eq!(*coords, vec![0, 0]),
+            other => panic!("expected a loss, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_state_change_callback_fires_once_on_loss() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&seen);
+        game.on_state_change(Box::new(move |state| sink.borrow_mut().push(state.clone())));
+
+        // A safe reveal doesn't change the state...
+        game.reveal(&vec![1, 0]).unwrap();
+        assert!(seen.borrow().is_empty());
+        // ...hitting the mine does, and moves after the loss are rejected.
+        game.reveal(&vec![0, 0]).unwrap();
+        assert_eq!(game.reveal(&vec![2, 2]), Err(GameError::GameOver));
+        assert_eq!(
+            *seen.borrow(),
+            vec![GameState::Lost {
+                reason: LossReason::HitMine(vec![0, 0])
+            }]
+        );
+    }
+
+    #[test]
+    fn test_only_loss_callback_fires_on_loss() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        let wins = Rc::new(RefCell::new(Vec::new()));
+        let losses = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&wins);
+        game.set_on_win(move |status| sink.borrow_mut().push(status.clone()));
+        let sink = Rc::clone(&losses);
+        game.set_on_loss(move |status| sink.borrow_mut().push(status.clone()));
+
+        game.reveal(&vec![1, 0]).unwrap();
+        assert!(losses.borrow().is_empty());
+        game.reveal(&vec![0, 0]).unwrap();
+
+        assert!(wins.borrow().is_empty());
+        let losses = losses.borrow();
+        assert_eq!(losses.len(), 1);
+        assert_eq!(losses[0], game.status());
+        assert_eq!(losses[0].revealed, 2);
+    }
+
+    #[test]
+    fn test_win_callback_receives_final_status() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        let wins = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&wins);
+        game.set_on_win(move |status| sink.borrow_mut().push(status.clone()));
+
+        // (2,2) is a zero, and its cascade opens every safe cell.
+        game.reveal(&vec![2, 2]).unwrap();
+        let wins = wins.borrow();
+        assert_eq!(wins.len(), 1);
+        assert_eq!(wins[0].revealed, 8);
+        assert!(matches!(wins[0].state, GameState::Won { .. }));
+    }
+
+    #[test]
+    fn test_chord_can_win_the_game() {
+        let board = Board::from_mines(vec![3, 1], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        game.reveal(&vec![1, 0]).unwrap();
+        game.toggle_flag(&vec![0, 0]).unwrap();
+
+        let result = game.chord(&vec![1, 0]).unwrap();
+        assert_eq!(
+            result,
+            MoveResult::Won {
+                revealed: vec![vec![2, 0]]
+            }
+        );
+    }
+
+    #[test]
+    fn test_simulate_reveal_leaves_original_untouched() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        game.set_first_click(FirstClick::Unprotected);
+        let losses = Rc::new(Cell::new(0));
+        let sink = Rc::clone(&losses);
+        game.set_on_loss(move |_| sink.set(sink.get() + 1));
+
+        let (after, result) = game.simulate_reveal(&vec![0, 0]).unwrap();
+        assert!(matches!(result, MoveResult::Lost { .. }));
+        assert!(matches!(after.state(), GameState::Lost { .. }));
+        assert_eq!(after.move_count(), 1);
+
+        // The real game hasn't moved, and its callback never fired.
+        assert_eq!(*game.state(), GameState::InProgress);
+        assert_eq!(game.move_count(), 0);
+        assert_eq!(game.board().revealed_count(), 0);
+        assert_eq!(game.elapsed(), None);
+        assert_eq!(losses.get(), 0);
+    }
+
+    #[test]
+    fn test_random_safe_start_opens_one_region() {
+        let mut game = Game::new_seeded(vec![16, 16], 40, 11).unwrap();
+        let result = game.random_safe_start().unwrap();
+
+        assert_eq!(*game.state(), GameState::InProgress);
+        assert!(!result.revealed().is_empty());
+        assert_eq!(game.board().revealed_count(), result.revealed().len());
+        // Everything revealed came from that one reveal.
+        for coords in result.revealed() {
+            assert_eq!(game.board().revealed_at_move(coords), Some(1));
+        }
+
+        // The same seed starts the same way.
+        let mut again = Game::new_seeded(vec![16, 16], 40, 11).unwrap();
+        assert_eq!(again.random_safe_start().unwrap(), result);
+    }
+
+    #[test]
+    fn test_reveal_smart_reveals_hidden_cells() {
+        let board = Board::from_mines(vec![4, 1], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        game.set_first_click(FirstClick::Unprotected);
+
+        let result = game.reveal_smart(&vec![1, 0]).unwrap();
+        assert_eq!(
+            result,
+            MoveResult::Continue {
+                revealed: vec![vec![1, 0]]
+            }
+        );
+    }
+
+    #[test]
+    fn test_reveal_smart_chords_satisfied_numbers() {
+        let board = Board::from_mines(vec![4, 1], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        game.set_first_click(FirstClick::Unprotected);
+        game.reveal_smart(&vec![1, 0]).unwrap();
+
+        // The '1' isn't satisfied yet, so clicking it again does nothing.
+        let result = game.reveal_smart(&vec![1, 0]).unwrap();
+        assert!(result.revealed().is_empty());
+
+        game.toggle_flag(&vec![0, 0]).unwrap();
+        let result = game.reveal_smart(&vec![1, 0]).unwrap();
+        // The chord opens (2,0), a zero that cascades to (3,0).
+        assert_eq!(
+            result,
+            MoveResult::Won {
+                revealed: vec![vec![2, 0], vec![3, 0]]
+            }
+        );
+    }
+
+    #[test]
+    fn test_events_for_a_cascade_and_a_win() {
+        // Row "* 1 . .": clicking (3,0) cascades to (2,0) and (1,0).
+        let board = Board::from_mines(vec![4, 1], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        game.record_events(true);
+        game.toggle_flag(&vec![0, 0]).unwrap();
+        game.reveal(&vec![3, 0]).unwrap();
+
+        let revealed = |x: usize, adjacent_mines: u16| Event::Revealed {
+            coords: vec![x, 0],
+            adjacent_mines,
+        };
+        assert_eq!(
+            game.take_events(),
+            vec![
+                Event::Flagged { coords: vec![0, 0] },
+                revealed(3, 0),
+                revealed(2, 0),
+                revealed(1, 1),
+                Event::Won,
+            ]
+        );
+        // The buffer was drained.
+        assert!(game.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_events_are_only_recorded_on_request() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        assert!(!game.records_events());
+        game.reveal(&vec![1, 1]).unwrap();
+        assert!(game.take_events().is_empty());
+
+        game.record_events(true);
+        game.toggle_flag(&vec![0, 0]).unwrap();
+        // Turning recording off drops what wasn't collected.
+        game.record_events(false);
+        assert!(game.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_events_for_a_loss() {
+        let board = Board::from_mines(vec![4, 1], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        game.record_events(true);
+        game.toggle_flag(&vec![1, 0]).unwrap();
+        game.toggle_flag(&vec![1, 0]).unwrap();
+        game.reveal(&vec![0, 0]).unwrap();
+
+        assert_eq!(
+            game.take_events(),
+            vec![
+                Event::Flagged { coords: vec![1, 0] },
+                Event::Unflagged { coords: vec![1, 0] },
+                Event::Lost { coords: vec![0, 0] },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reveal_after_loss_is_game_over() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+
+        game.reveal(&vec![0, 0]).unwrap();
+        assert_eq!(game.reveal(&vec![2, 2]), Err(GameError::GameOver));
+        assert_eq!(game.reveal_all_unflagged(), Err(GameError::GameOver));
+        // The rejected moves changed nothing.
+        assert!(!game.board().is_revealed(&vec![2, 2]));
+        assert_eq!(game.move_count(), 1);
+    }
+
+    #[test]
+    fn test_moves_off_the_board_are_rejected() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+
+        // `[1, 1, 0]` would have landed on (1,1) of a 3x3 board.
+        assert_eq!(
+            game.reveal(&vec![1, 1, 0]),
+            Err(GameError::InvalidCoords(BoardError::WrongCoordinateCount {
+                expected: 2,
+                actual: 3,
+            }))
+        );
+        assert_eq!(
+            game.reveal(&vec![3, 0]),
+            Err(GameError::InvalidCoords(BoardError::OutOfBounds {
+                coords: vec![3, 0],
+            }))
+        );
+        assert_eq!(
+            game.chord(&vec![1]),
+            Err(GameError::InvalidCoords(BoardError::WrongCoordinateCount {
+                expected: 2,
+                actual: 1,
+            }))
+        );
+        assert!(matches!(
+            game.reveal_smart(&vec![0, 7]),
+            Err(GameError::InvalidCoords(BoardError::OutOfBounds { .. }))
+        ));
+
+        // The rejected moves changed nothing, not even the first click.
+        assert_eq!(game.board().revealed_count(), 0);
+        assert_eq!(game.move_count(), 0);
+        assert!(!game.first_reveal_done);
+        assert_eq!(game.elapsed(), None);
+    }
+
+    #[test]
+    fn test_winning_reveal_returns_won_with_revealed_cells() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+
+        assert_eq!(
+            game.reveal(&vec![1, 0]).unwrap(),
+            MoveResult::Continue {
+                revealed: vec![vec![1, 0]]
+            }
+        );
+        game.reveal(&vec![0, 1]).unwrap();
+        game.reveal(&vec![1, 1]).unwrap();
+
+        // The cascade from the far corner opens the remaining five cells.
+        let result = game.reveal(&vec![2, 2]).unwrap();
+        let mut revealed = result.revealed().to_vec();
+        revealed.sort();
+        assert!(matches!(result, MoveResult::Won { .. }));
+        assert_eq!(
+            revealed,
+            vec![vec![0, 2], vec![1, 2], vec![2, 0], vec![2, 1], vec![2, 2]]
+        );
+    }
+
+    #[test]
+    fn test_losing_reveal_returns_lost() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+        assert_eq!(
+            game.reveal(&vec![0, 0]).unwrap(),
+            MoveResult::Lost {
+                revealed: vec![vec![0, 0]]
+            }
+        );
+    }
+
+    #[test]
+    fn test_new_with_auto_open_starts_with_an_opening() {
+        let game = Game::new_with_auto_open(vec![10, 10], 10, 3).unwrap();
+        assert!(game.board().revealed_count() > 1);
+        assert_eq!(*game.state(), GameState::InProgress);
+        // The opening is a gift, not a move.
+        assert_eq!(game.move_count(), 0);
+        assert_eq!(game.elapsed(), None);
+
+        // No zero cell on a board that is all mines but one: start normally.
+        let game = Game::new_with_auto_open(vec![3, 3], 8, 3).unwrap();
+        assert_eq!(game.board().revealed_count(), 0);
+    }
+
+    #[test]
+    fn test_win_records_moves_and_time() {
+        let board = Board::from_mines(vec![3, 3], &[vec![0, 0]]);
+        let mut game = Game::from_board(board);
+
+        // Two numbered cells first, then the cascade that finishes the game.
+        game.reveal(&vec![1, 1]).unwrap();
+        game.reveal(&vec![1, 0]).unwrap();
+        game.reveal(&vec![2, 2]).unwrap();
+        match game.state() {
+            GameState::Won { moves, elapsed } => {
+                assert_eq!(*moves, 3);
+                assert_eq!(*elapsed, game.elapsed());
+                assert!(elapsed.is_some());
+            }
+            other => panic!("expected a win, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_reveal_all_unflagged_with_correct_flags_wins() {
+        let board = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![3, 3]]);
+        let mut game = Game::from_board(board);
+        game.toggle_flag(&vec![0, 0]).unwrap();
+        game.toggle_flag(&vec![3, 3]).unwrap();
+
+        game.reveal_all_unflagged().unwrap();
+        assert!(matches!(game.state(), GameState::Won { .. }));
+    }
+
+    #[test]
+    fn test_reveal_all_unflagged_with_wrong_flags_loses() {
+        let board = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![3, 3]]);
+        let mut game = Game::from_board(board);
+        game.toggle_flag(&vec![0, 0]).unwrap();
+        game.toggle_flag(&vec![2, 2]).unwrap(); // Wrong: (3,3) is the real mine.
+
+        game.reveal_all_unflagged().unwrap();
+        assert_eq!(
+            *game.state(),
+            GameState::Lost {
+                reason: LossReason::HitMine(vec![3, 3])
+            }
+        );
+        assert_eq!(game.board().cell_at(15).state, CellState::Revealed);
+    }
+
+    #[test]
+    fn test_win_flags_the_remaining_mines() {
+        let mines = [vec![0, 0], vec![3, 3], vec![0, 3]];
+        let board = Board::from_mines(vec![4, 4], &mines);
+        let mut game = Game::from_board(board);
+        game.record_events(true);
+        game.cycle_mark(&vec![0, 3]).unwrap();
+        game.take_events();
+
+        // Reveal every safe cell, one by one.
+        for index in 0..16 {
+            let coords = to_coords(index, &[4, 4]);
+            if !mines.contains(&coords) && !game.board().is_revealed(&coords) {
+                game.reveal(&coords).unwrap();
+            }
+        }
+        assert!(matches!(game.state(), GameState::Won { .. }));
+        for cell in game.board().cells() {
+            match cell.kind {
+                CellKind::Mine => assert_eq!(cell.state, CellState::Flagged),
+                CellKind::Empty { .. } => assert_eq!(cell.state, CellState::Revealed),
+            }
+        }
+
+        // The (0,3) mine was already flagged by the player; the other two
+        // are flagged automatically, just before the win is announced.
+        let events = game.take_events();
+        let tail = &events[events.len() - 3..];
+        assert_eq!(
+            tail,
+            [
+                Event::Flagged { coords: vec![0, 0] },
+                Event::Flagged { coords: vec![3, 3] },
+                Event::Won
+            ]
+        );
+    }
+
+    #[test]
+    fn test_origin_one_counts_from_the_top_left() {
+        let mut game = Game::from_board(Board::from_mines(vec![4, 3], &[vec![3, 2]]));
+        game.set_origin(1);
+
+        let top_left = game.parse_coords("1,1").unwrap();
+        assert_eq!(top_left, vec![0, 0]);
+        assert_eq!(game.format_coords(&top_left), "1,1");
+        assert_eq!(game.format_coords(&vec![3, 2]), "4,3");
+        assert_eq!(
+            game.parse_coords("2, 0"),
+            Err(ParseError::BelowOrigin {
+                position: 1,
+                origin: 1
+            })
+        );
+
+        assert_eq!(game.validate_coords(&vec![4, 3]), Ok(()));
+        for outside in [vec![5, 1], vec![0, 1]] {
+            assert_eq!(
+                game.validate_coords(&outside),
+                Err(BoardError::OutOfBounds { coords: outside })
+            );
+        }
+
+        // The default stays 0-based.
+        game.set_origin(0);
+        assert_eq!(game.parse_coords("1,1").unwrap(), vec![1, 1]);
+        assert_eq!(game.validate_coords(&vec![0, 0]), Ok(()));
+    }
+
+    #[test]
+    fn test_move_count_counts_a_cascade_once() {
+        // The mines fence off the corner at (5, 5), so the cascade from
+        // (0, 0) can't reach it and the game goes on.
+        let board = Board::from_mines(vec![6, 6], &[vec![5, 4], vec![4, 5]]);
+        let mut game = Game::from_board(board);
+
+        let result = game.reveal(&vec![0, 0]).unwrap();
+        assert!(matches!(result, MoveResult::Continue { .. }));
+        assert!(result.revealed().len() > 1);
+        assert_eq!(game.move_count(), 1);
+
+        game.toggle_flag(&vec![5, 4]).unwrap();
+        assert_eq!(game.move_count(), 2);
+        // Flagging a revealed cell changes nothing, so it isn't a move.
+        game.toggle_flag(&vec![0, 0]).unwrap();
+        assert_eq!(game.move_count(), 2);
+    }
+
+    #[test]
+    fn test_move_count_skips_moves_that_change_nothing() {
+        let board = Board::from_mines(vec![6, 6], &[vec![5, 4], vec![4, 5]]);
+        let mut game = Game::from_board(board);
+        game.reveal(&vec![0, 0]).unwrap();
+        game.toggle_flag(&vec![5, 4]).unwrap();
+        assert_eq!(game.move_count(), 2);
+
+        // Revealing a revealed or a flagged cell does nothing.
+        game.reveal(&vec![0, 0]).unwrap();
+        game.reveal(&vec![5, 4]).unwrap();
+        // Neither does a double-click on the "2" at (4, 4) with one flag.
+        let chord = game.reveal_smart(&vec![4, 4]).unwrap();
+        assert!(chord.revealed().is_empty());
+        assert_eq!(game.move_count(), 2);
+
+        // Once the flags add up, the chord is a move, and the win records
+        // only the moves that counted.
+        game.toggle_flag(&vec![4, 5]).unwrap();
+        game.chord(&vec![4, 4]).unwrap();
+        assert_eq!(game.move_count(), 4);
+        assert!(matches!(game.state(), GameState::Won { moves: 4, .. }));
+    }
+
+    #[test]
+    fn test_reveal_all_debug_does_not_win() {
+        let board = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![3, 3]]);
+        let mut game = Game::from_board(board);
+        game.reveal_all_debug().unwrap();
+
+        assert!(game.board().cells().all(|c| c.state == CellState::Revealed));
+        assert_eq!(
+            *game.state(),
+            GameState::Lost {
+                reason: LossReason::Spoiled
+            }
+        );
+        assert_eq!(game.reveal(&vec![1, 1]), Err(GameError::GameOver));
+    }
+
+    #[test]
+    fn test_seeded_game_reports_its_seed() {
+        let game = Game::new_seeded(vec![9, 9], 10, 1234).unwrap();
+        assert_eq!(game.seed(), Some(1234));
+        let again = Game::new_seeded(vec![9, 9], 10, game.seed().unwrap()).unwrap();
+        assert!(again.board().same_layout(game.board()));
+
+        let opened = Game::new_with_auto_open(vec![9, 9], 10, 77).unwrap();
+        assert_eq!(opened.seed(), Some(77));
+        assert_eq!(opened.clone().seed(), Some(77));
+
+        assert_eq!(Game::new(vec![9, 9], 10).unwrap().seed(), None);
+        let board = Board::from_mines(vec![2, 2], &[]);
+        assert_eq!(Game::from_board(board).seed(), None);
+    }
+
+    #[test]
+    fn test_redacted_clone_hides_unopened_mines() {
+        let board = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![3, 3]]);
+        let mut game = Game::from_board(board);
+        game.reveal(&vec![1, 0]).unwrap();
+        game.toggle_flag(&vec![0, 0]).unwrap();
+
+        let redacted = game.redacted_clone();
+        // A flagged mine, a hidden mine and a hidden safe cell all look alike.
+        for coords in [vec![0, 0], vec![3, 3], vec![2, 2]] {
+            assert_eq!(redacted.contents_at(&coords), None);
+        }
+        assert!(
+            redacted
+                .contents
+                .iter()
+                .flatten()
+                .all(|k| *k != CellKind::Mine)
+        );
+        assert_eq!(redacted.state_at(&vec![3, 3]), Some(&CellState::Hidden));
+
+        // What the player has seen survives.
+        assert_eq!(
+            redacted.contents_at(&vec![1, 0]),
+            Some(&CellKind::Empty { adjacent_mines: 1 })
+        );
+        assert_eq!(redacted.state_at(&vec![0, 0]), Some(&CellState::Flagged));
+        assert_eq!(redacted.moves, game.move_count());
+        assert_eq!(redacted.status.flags, 1);
+        assert_eq!(redacted.status.state, *game.state());
+        assert_eq!(redacted.state_at(&vec![4, 0]), None);
+
+        // A hit mine was seen, so it stays visible.
+        game.reveal(&vec![3, 3]).unwrap();
+        let lost = game.redacted_clone();
+        assert_eq!(lost.contents_at(&vec![3, 3]), Some(&CellKind::Mine));
+        assert_eq!(lost.contents_at(&vec![0, 0]), None);
+    }
+
+    #[test]
+    fn test_practice_mode_survives_mine_hits() {
+        let board = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![3, 3], vec![0, 3]]);
+        let mut game = Game::from_board(board);
+        game.record_events(true);
+        game.set_mode(GameMode::Practice);
+
+        assert_eq!(
+            game.reveal(&vec![0, 0]),
+            Ok(MoveResult::Continue {
+                revealed: vec![vec![0, 0]]
+            })
+        );
+        game.reveal(&vec![3, 3]).unwrap();
+        assert_eq!(*game.state(), GameState::InProgress);
+        assert!(game.board().is_revealed(&vec![3, 3]));
+        assert_eq!(game.mines_hit(), &[vec![0, 0], vec![3, 3]]);
+        assert_eq!(
+            game.take_events(),
+            vec![
+                Event::MineHit { coords: vec![0, 0] },
+                Event::MineHit { coords: vec![3, 3] },
+            ]
+        );
+
+        // Playing on still works, and uncovering every safe cell wins even
+        // though the same move hits the last mine.
+        game.reveal_all_unflagged().unwrap();
+        assert!(matches!(game.state(), GameState::Won { .. }));
+        assert_eq!(game.mines_hit().len(), 3);
+
+        // Opening everything at once records every mine it uncovers, not
+        // just the first.
+        let board = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![3, 3]]);
+        let mut game = Game::from_board(board);
+        game.set_mode(GameMode::Practice);
+        game.record_events(true);
+        game.reveal_all_unflagged().unwrap();
+        assert_eq!(game.mines_hit(), &[vec![0, 0], vec![3, 3]]);
+        let hits: Vec<Event> = game
+            .take_events()
+            .into_iter()
+            .filter(|event| matches!(event, Event::MineHit { .. }))
+            .collect();
+        assert_eq!(hits.len(), 2);
+        assert!(matches!(game.state(), GameState::Won { .. }));
+
+        // The same first move loses a standard game.
+        let mut standard = Game::from_board(Board::from_mines(vec![4, 4], &[vec![0, 0]]));
+        assert!(matches!(
+            standard.reveal(&vec![0, 0]),
+            Ok(MoveResult::Lost { .. })
+        ));
+        assert!(standard.mines_hit().is_empty());
+    }
+
+    #[test]
+    fn test_daily_is_deterministic_per_date() {
+        let today = Game::daily(vec![16, 16], 40, (2024, 1, 31)).unwrap();
+        let same_day = Game::daily(vec![16, 16], 40, (2024, 1, 31)).unwrap();
+        assert!(today.board().same_layout(same_day.board()));
+
+        // Different dates should (with overwhelming probability) differ.
+        let tomorrow = Game::daily(vec![16, 16], 40, (2024, 2, 1)).unwrap();
+        let next_year = Game::daily(vec![16, 16], 40, (2025, 1, 31)).unwrap();
+        assert!(!today.board().same_layout(tomorrow.board()));
+        assert!(!today.board().same_layout(next_year.board()));
+    }
+
+    #[test]
+    fn test_safe_region_falls_back_to_safe_cell() {
+        // 8 mines on a 3x3 board: there is no room to clear the whole
+        // neighborhood of the center, but the center itself can be cleared.
+        let mut game = Game::new(vec![3, 3], 8).unwrap();
+        game.set_first_click(FirstClick::SafeRegion);
+
+        let coords = vec![1, 1];
+        game.reveal(&coords).unwrap();
+
+        assert!(!matches!(game.state(), GameState::Lost { .. }));
+        assert_eq!(game.board().num_mines(), 8);
     }
 }
@@ -9,16 +9,28 @@
 // This modular design enhances maintainability and makes the codebase easier to understand.
 
 // Declare the modules that make up the library.
+pub mod bitset;
 pub mod board;
+pub mod campaign;
 pub mod cell;
 pub mod coordinates;
+pub mod error;
 pub mod game;
+pub mod render;
+pub mod snapshot;
+pub mod solver;
 
 // The `prelude` module is a common pattern in Rust libraries.
 // It re-exports the most commonly used items for convenience.
 pub mod prelude {
-    pub use crate::board::Board;
-    pub use crate::cell::{Cell, CellKind, CellState};
+    pub use crate::board::{
+        Adjacency, Board, FloodPolicy, MineReport, NeighborSummary, Placement, RevealOutcome,
+    };
+    pub use crate::campaign::Campaign;
+    pub use crate::cell::{Cell, CellKind, CellState, MarkCycle};
     pub use crate::coordinates::{to_coords, to_index, Coordinates};
-    pub use crate::game::{Game, GameState};
+    pub use crate::error::{BoardError, GameError, ParseError, SnapshotError};
+    pub use crate::game::{Event, Game, GameMode, GameState, LossReason, MoveResult, RedactedGame};
+    pub use crate::render::RenderTheme;
+    pub use crate::solver::{Hint, HintKind};
 }
@@ -13,7 +13,15 @@ fn main() {
     // Create a new 2D game board, 10x10 with 10 mines.
     let dimensions = vec![10, 10];
     let num_mines = 10;
-    let game = Game::new(dimensions, num_mines);
+    // Creating a game can fail (for example, if there are more mines than
+    // cells), so we report the error instead of crashing.
+    let game = match Game::new(dimensions, num_mines) {
+        Ok(game) => game,
+        Err(err) => {
+            eprintln!("Could not create the game: {err}");
+            return;
+        }
+    };
 
     println!("Game created. Current state: {:?}", game.state());
 
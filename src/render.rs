@@ -0,0 +1,316 @@
+// src/render.rs
+
+//! The `render` module turns a board into text for terminals and logs.
+//!
+//! Rendering is kept separate from the board logic so that the game engine
+//! doesn't care how it is displayed: a graphical front-end can ignore this
+//! module entirely. Only information the player is allowed to see is drawn;
+//! hidden cells never give away what is underneath them.
+//!
+//! A terminal can only show two dimensions at a time, so higher-dimensional
+//! boards are drawn as a series of 2D layers (slices).
+
+use crate::board::Board;
+use crate::cell::{CellKind, CellState};
+use crate::coordinates::Coordinates;
+use crate::error::BoardError;
+
+/// The glyph drawn for a cell that hasn't been revealed.
+const HIDDEN: &str = "#";
+/// The glyph drawn for a flagged cell.
+const FLAGGED: &str = "F";
+/// The glyph drawn for a question-marked cell.
+const QUESTIONED: &str = "?";
+/// The glyph drawn for a revealed mine.
+const MINE: &str = "*";
+/// The glyph drawn for a revealed cell with no adjacent mines.
+const EMPTY_ZERO: &str = ".";
+
+/// The glyphs used to draw a board, for players who want different symbols
+/// (another script, emoji, or just a different taste).
+///
+/// `RenderTheme::default()` draws exactly what `render_2d` draws.
+#[derive(Debug, Clone)]
+pub struct RenderTheme {
+    /// Drawn for a cell that hasn't been revealed.
+    pub hidden: String,
+    /// Drawn for a flagged cell.
+    pub flagged: String,
+    /// Drawn for a question-marked cell.
+    pub questioned: String,
+    /// Drawn for a revealed mine.
+    pub mine: String,
+    /// Drawn for a revealed cell with no adjacent mines.
+    pub empty_zero: String,
+    /// Turns the adjacent mine count of any other revealed cell into its
+    /// glyph. Counts can go past 9 in 3D and beyond, so this is a function
+    /// rather than a fixed table of digits.
+    pub digit: fn(u16) -> String,
+}
+
+impl Default for RenderTheme {
+    fn default() -> Self {
+        Self {
+            hidden: HIDDEN.to_string(),
+            flagged: FLAGGED.to_string(),
+            questioned: QUESTIONED.to_string(),
+            mine: MINE.to_string(),
+            empty_zero: EMPTY_ZERO.to_string(),
+            digit: |count| count.to_string(),
+        }
+    }
+}
+
+impl Board {
+    /// Renders a 2D board as a grid of text, one row per line.
+    ///
+    /// The first axis runs left to right and the second top to bottom, so the
+    /// cell at `[x, y]` is in column `x` of line `y`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BoardError::WrongDimensionality` if the board isn't 2D.
+    pub fn render_2d(&self) -> Result<String, BoardError> {
+        self.render_2d_themed(&RenderTheme::default())
+    }
+
+    /// Renders a 2D board like `render_2d`, but with the glyphs of `theme`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BoardError::WrongDimensionality` if the board isn't 2D.
+    pub fn render_2d_themed(&self, theme: &RenderTheme) -> Result<String, BoardError> {
+        self.expect_dimensions(2)?;
+        Ok(self.render_layer(&[], self.glyph_width(theme), theme))
+    }
+
+    /// Renders a 3D board as a stack of labeled 2D layers, one per value of
+    /// the third axis (`z=0`, `z=1`, ...). The header uses the board's axis
+    /// label, so after `set_axis_labels` it reads e.g. `depth=0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BoardError::WrongDimensionality` if the board isn't 3D.
+    pub fn render_3d(&self) -> Result<String, BoardError> {
+        self.expect_dimensions(3)?;
+        // One width for the whole board keeps the layers aligned with each
+        // other, too.
+        let theme = RenderTheme::default();
+        let width = self.glyph_width(&theme);
+        let label = &self.axis_labels()[2];
+        Ok((0..self.dimensions()[2])
+            .map(|z| format!("{label}={z}\n{}", self.render_layer(&[z], width, &theme)))
+            .collect())
+    }
+
+    /// Returns the size in pixels of a 2D board drawn as a grid of square
+    /// cells, each `cell_px` pixels wide, as `(width, height)`.
+    ///
+    /// Returns `None` if the board isn't 2D, if `cell_px` is zero, or if the
+    /// size doesn't fit in a `usize`.
+    pub fn layout_2d(&self, cell_px: usize) -> Option<(usize, usize)> {
+        if self.dimensions().len() != 2 || cell_px == 0 {
+            return None;
+        }
+        let width = self.dimensions()[0].checked_mul(cell_px)?;
+        let height = self.dimensions()[1].checked_mul(cell_px)?;
+        Some((width, height))
+    }
+
+    /// Maps a pixel position on a 2D board drawn with `layout_2d` back to
+    /// the coordinates of the cell under it, e.g. to handle a mouse click.
+    ///
+    /// Pixel `(0, 0)` is the top-left corner, matching `render_2d`: the first
+    /// axis runs left to right and the second top to bottom.
+    ///
+    /// Returns `None` if the board isn't 2D, `cell_px` is zero, or the
+    /// position lies outside the board.
+    pub fn pixel_to_coords(&self, x: usize, y: usize, cell_px: usize) -> Option<Coordinates> {
+        let (width, height) = self.layout_2d(cell_px)?;
+        if x >= width || y >= height {
+            return None;
+        }
+        Some(vec![x / cell_px, y / cell_px])
+    }
+
+    /// Returns an error unless the board has exactly `expected` dimensions.
+    fn expect_dimensions(&self, expected: usize) -> Result<(), BoardError> {
+        let actual = self.dimensions().len();
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(BoardError::WrongDimensionality { expected, actual })
+        }
+    }
+
+    /// Renders one 2D slice of the board.
+    ///
+    /// The slice spans the first two axes; every further axis is held at the
+    /// value given in `fixed` (so `fixed[0]` is the third axis, and so on).
+    /// This is the single building block used for boards of any dimension.
+    ///
+    /// Every glyph is right-aligned in a column `width` characters wide.
+    fn render_layer(&self, fixed: &[usize], width: usize, theme: &RenderTheme) -> String {
+        let dimensions = self.dimensions();
+        let mut lines = Vec::with_capacity(dimensions[1]);
+        for y in 0..dimensions[1] {
+            let row: Vec<String> = (0..dimensions[0])
+                .map(|x| {
+                    let mut coords = vec![x, y];
+                    coords.extend_from_slice(fixed);
+                    let glyph = self.cell_glyph(self.index_of(&coords), theme);
+                    format!("{glyph:>width$}")
+                })
+                .collect();
+            lines.push(row.join(" "));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Returns the width of the widest glyph on the board.
+    ///
+    /// Counts of 10 or more take two characters, which would shift every
+    /// cell after them out of its column. Padding all glyphs to the widest
+    /// one keeps the grid aligned. Only visible glyphs are measured, so the
+    /// width can't give away anything about hidden cells.
+    ///
+    /// Widths are counted in characters, not bytes, so themes with non-ASCII
+    /// glyphs line up too.
+    fn glyph_width(&self, theme: &RenderTheme) -> usize {
+        (0..self.cell_count())
+            .map(|index| self.cell_glyph(index, theme).chars().count())
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Returns the glyph for the cell at flat `index`, as the player sees it.
+    fn cell_glyph(&self, index: usize, theme: &RenderTheme) -> String {
+        match (&self.states[index], self.kind_at(index)) {
+            (CellState::Hidden, _) => theme.hidden.clone(),
+            (CellState::Flagged, _) => theme.flagged.clone(),
+            (CellState::Questioned, _) => theme.questioned.clone(),
+            (CellState::Revealed, CellKind::Mine) => theme.mine.clone(),
+            (CellState::Revealed, CellKind::Empty { adjacent_mines: 0 }) => {
+                theme.empty_zero.clone()
+            }
+            // In 3D and beyond a cell can touch more than 9 mines, so counts
+            // are formatted rather than looked up as single digits.
+            (CellState::Revealed, CellKind::Empty { adjacent_mines }) => {
+                (theme.digit)(adjacent_mines)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderTheme;
+    use crate::board::Board;
+    use crate::error::BoardError;
+
+    #[test]
+    fn test_render_2d() {
+        let mut board = Board::from_mines(vec![3, 2], &[vec![0, 0]]);
+        board.reveal(&vec![1, 0]);
+        board.toggle_flag(&vec![0, 0]);
+
+        assert_eq!(board.render_2d().unwrap(), "F 1 #\n# # #\n");
+    }
+
+    #[test]
+    fn test_render_2d_pads_multi_digit_counts() {
+        let mut board = Board::from_mines(vec![3, 2], &[vec![0, 0]]);
+        board.reveal(&vec![1, 0]);
+        // No 2D neighborhood can reach ten mines yet, so fake a count that
+        // a denser neighborhood could produce.
+        board.counts[1] = 12;
+
+        let rendered = board.render_2d().unwrap();
+        assert_eq!(rendered, " # 12  #\n #  #  #\n");
+        // Every column is two characters wide, so every row has the same length.
+        assert!(rendered.lines().all(|row| row.len() == 8));
+    }
+
+    #[test]
+    fn test_render_2d_themed() {
+        let mut board = Board::from_mines(vec![3, 2], &[vec![0, 0]]);
+        board.reveal(&vec![1, 0]);
+        board.reveal(&vec![2, 1]);
+        board.toggle_flag(&vec![0, 0]);
+
+        let theme = RenderTheme {
+            hidden: "□".to_string(),
+            flagged: "⚑".to_string(),
+            empty_zero: "·".to_string(),
+            digit: |count| ["零", "一", "二", "三"][count as usize].to_string(),
+            ..RenderTheme::default()
+        };
+        assert_eq!(board.render_2d_themed(&theme).unwrap(), "⚑ 一 ·\n□ 一 ·\n");
+
+        // The default theme is what `render_2d` draws.
+        assert_eq!(
+            board.render_2d_themed(&RenderTheme::default()).unwrap(),
+            board.render_2d().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_render_3d_layers() {
+        let mut board = Board::from_mines(vec![2, 2, 2], &[vec![1, 1, 1]]);
+        board.reveal(&vec![0, 0, 0]);
+
+        let rendered = board.render_3d().unwrap();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines, vec!["z=0", "1 #", "# #", "z=1", "# #", "# #"]);
+
+        // Each layer is a 2x2 grid.
+        for layer in rendered.split("z=").skip(1) {
+            let rows: Vec<&str> = layer.lines().skip(1).collect();
+            assert_eq!(rows.len(), 2);
+            assert!(rows.iter().all(|row| row.split(' ').count() == 2));
+        }
+    }
+
+    #[test]
+    fn test_render_3d_uses_axis_labels() {
+        let mut board = Board::from_mines(vec![2, 2, 2], &[]);
+        board.set_axis_labels(vec!["col".into(), "row".into(), "depth".into()]);
+
+        let rendered = board.render_3d().unwrap();
+        assert!(rendered.contains("depth=0\n"));
+        assert!(rendered.contains("depth=1\n"));
+        assert!(!rendered.contains("z="));
+        assert_eq!(board.format_coords(&vec![1, 0, 1]), "col=1, row=0, depth=1");
+    }
+
+    #[test]
+    fn test_layout_2d_and_pixel_to_coords() {
+        let board = Board::from_mines(vec![4, 3], &[]);
+        assert_eq!(board.layout_2d(16), Some((64, 48)));
+        assert_eq!(board.layout_2d(0), None);
+
+        // Pixel (37, 20) lies in column 2 (32..48) and row 1 (16..32).
+        assert_eq!(board.pixel_to_coords(37, 20, 16), Some(vec![2, 1]));
+        assert_eq!(board.pixel_to_coords(0, 0, 16), Some(vec![0, 0]));
+        assert_eq!(board.pixel_to_coords(63, 47, 16), Some(vec![3, 2]));
+        assert_eq!(board.pixel_to_coords(64, 0, 16), None);
+
+        let board = Board::from_mines(vec![2, 2, 2], &[]);
+        assert_eq!(board.layout_2d(16), None);
+        assert_eq!(board.pixel_to_coords(1, 1, 16), None);
+    }
+
+    #[test]
+    fn test_render_rejects_wrong_dimensionality() {
+        let board = Board::from_mines(vec![2, 2], &[]);
+        assert_eq!(
+            board.render_3d(),
+            Err(BoardError::WrongDimensionality {
+                expected: 3,
+                actual: 2
+            })
+        );
+        let board = Board::from_mines(vec![2, 2, 2], &[]);
+        assert!(board.render_2d().is_err());
+    }
+}
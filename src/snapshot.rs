@@ -0,0 +1,624 @@
+// src/snapshot.rs
+
+//! The `snapshot` module turns a game into plain data and back.
+//!
+//! A `GameSnapshot` is what gets saved to disk or sent over the network, for
+//! example to keep the boards of co-op players in sync. It deliberately
+//! contains only simple values (numbers and cell states), so it can be
+//! serialized with any format; enable the `serde` feature to derive
+//! `Serialize` and `Deserialize` for it.
+//!
+//! Every snapshot carries an explicit format `version`. Data that outlives the
+//! program that wrote it must be read back by *later* versions of the code, so
+//! `GameSnapshot::into_game` dispatches on the version. When the format
+//! changes, bump `SNAPSHOT_VERSION` and add a migration arm for the old
+//! version there; a snapshot from an unknown version is rejected rather than
+//! misread.
+//!
+//! For large boards, text formats such as JSON spend several bytes on every
+//! cell. `Game::save_binary` offers a compact alternative that stores one bit
+//! per cell for the mines and two bits per cell for the states.
+
+use crate::bitset::Bitset;
+use crate::board::{Adjacency, Board};
+use crate::cell::CellState;
+use crate::error::SnapshotError;
+use crate::game::{Game, GameMode};
+
+/// The snapshot format version written by this build.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// The first bytes of every binary save, so that other files are recognized
+/// and rejected straight away.
+pub const BINARY_MAGIC: [u8; 4] = *b"NDMS";
+
+/// The binary save format version written by this build.
+///
+/// The binary layout is versioned separately from `SNAPSHOT_VERSION`: it is
+/// only one of many ways to encode a snapshot.
+pub const BINARY_VERSION: u8 = 2;
+
+/// A compact, versioned copy of a game's data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameSnapshot {
+    /// The format version. See the module documentation.
+    pub version: u32,
+    /// The dimensions of the board.
+    pub dimensions: Vec<usize>,
+    /// The flat indices of every mine. Storing indices instead of a full grid
+    /// of cell kinds keeps snapshots small, since most cells are not mines.
+    pub mines: Vec<usize>,
+    /// The state of every cell, in flat index order.
+    pub states: Vec<CellState>,
+    /// How many moves the player has made.
+    pub moves: usize,
+    /// Which cells count as neighbors. The numbers depend on it, so a board
+    /// restored with the wrong rule would show different numbers.
+    ///
+    /// This and the fields below were added in version 2. Version 1
+    /// snapshots don't have them (they deserialize to their defaults) and
+    /// are always read with the settings every game had at the time: Moore
+    /// adjacency, no wrapping, cascading on, nothing frozen, standard mode.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub adjacency: Adjacency,
+    /// For every axis, whether its edges wrap around. See
+    /// `Board::set_wrapping_axes`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub wrap: Vec<bool>,
+    /// Whether revealing a zero cell opens its neighbors. See
+    /// `Board::set_cascade`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cascade: bool,
+    /// The flat indices of every frozen cell. See `Board::set_frozen`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub frozen: Vec<usize>,
+    /// Whether hitting a mine ends the game. Without it, a practice game
+    /// with a mine hit would load as lost.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mode: GameMode,
+}
+
+impl Game {
+    /// Captures the game's data in a `GameSnapshot`.
+    pub fn snapshot(&self) -> GameSnapshot {
+        let board = self.board();
+        GameSnapshot {
+            version: SNAPSHOT_VERSION,
+            dimensions: board.dimensions().to_vec(),
+            mines: (0..board.cell_count())
+                .filter(|&i| board.mines.get(i))
+                .collect(),
+            states: board.states.clone(),
+            moves: self.move_count(),
+            adjacency: board.adjacency(),
+            wrap: board.wrapping_axes(),
+            cascade: board.cascade(),
+            frozen: (0..board.cell_count())
+                .filter(|&i| board.is_frozen(&board.coords_of(i)))
+                .collect(),
+            mode: self.mode(),
+        }
+    }
+
+    /// Encodes the game in the compact binary save format.
+    ///
+    /// The layout is, with all integers little-endian:
+    ///
+    /// 1. The 4 magic bytes `BINARY_MAGIC` and the `BINARY_VERSION` byte.
+    /// 2. The number of dimensions as a `u32`, then each dimension as a `u64`.
+    /// 3. The number of moves as a `u64`.
+    /// 4. The adjacency rule as one byte (0 Moore, 1 orthogonal).
+    /// 5. One byte of options: bit 0 is set if cascading is on, bit 1 in
+    ///    `GameMode::Practice`.
+    /// 6. One bit per axis, set if it wraps around, 8 axes per byte, lowest
+    ///    bit first.
+    /// 7. The number of frozen cells as a `u64`, then each one's flat index
+    ///    as a `u64`. Few cells are ever frozen, so a list beats a bitmap.
+    /// 8. One bit per cell, set for mines, in flat index order (8 cells per
+    ///    byte, lowest bit first).
+    /// 9. Two bits per cell for the state (0 hidden, 1 revealed, 2 flagged,
+    ///    3 questioned), 4 cells per byte, lowest bits first.
+    ///
+    /// Version 1 saves don't have items 4 to 7, and are loaded with the
+    /// defaults described at `GameSnapshot::adjacency`.
+    pub fn save_binary(&self) -> Vec<u8> {
+        let board = self.board();
+        let total_cells = board.cell_count();
+
+        let snapshot = self.snapshot();
+        let axes = board.dimensions().len();
+        let mut bytes =
+            Vec::with_capacity(27 + 8 * (axes + snapshot.frozen.len()) + total_cells / 2);
+        bytes.extend_from_slice(&BINARY_MAGIC);
+        bytes.push(BINARY_VERSION);
+        bytes.extend_from_slice(&(board.dimensions().len() as u32).to_le_bytes());
+        for &size in board.dimensions() {
+            bytes.extend_from_slice(&(size as u64).to_le_bytes());
+        }
+        bytes.extend_from_slice(&(self.move_count() as u64).to_le_bytes());
+        bytes.push(match snapshot.adjacency {
+            Adjacency::Moore => 0,
+            Adjacency::Orthogonal => 1,
+        });
+        let practice = snapshot.mode == GameMode::Practice;
+        bytes.push(u8::from(snapshot.cascade) | u8::from(practice) << 1);
+        let mut wrap = vec![0_u8; axes.div_ceil(8)];
+        for (axis, _) in snapshot.wrap.iter().enumerate().filter(|(_, w)| **w) {
+            wrap[axis / 8] |= 1 << (axis % 8);
+        }
+        bytes.extend_from_slice(&wrap);
+        bytes.extend_from_slice(&(snapshot.frozen.len() as u64).to_le_bytes());
+        for &index in &snapshot.frozen {
+            bytes.extend_from_slice(&(index as u64).to_le_bytes());
+        }
+
+        let mut mines = vec![0_u8; total_cells.div_ceil(8)];
+        let mut states = vec![0_u8; total_cells.div_ceil(4)];
+        for (index, state) in board.states.iter().enumerate() {
+            if board.mines.get(index) {
+                mines[index / 8] |= 1 << (index % 8);
+            }
+            let code = match state {
+                CellState::Hidden => 0,
+                CellState::Revealed => 1,
+                CellState::Flagged => 2,
+                CellState::Questioned => 3,
+            };
+            states[index / 4] |= code << (2 * (index % 4));
+        }
+        bytes.extend_from_slice(&mines);
+        bytes.extend_from_slice(&states);
+        bytes
+    }
+
+    /// Decodes a game saved with `save_binary`.
+    ///
+    /// # Errors
+    ///
+    /// * `SnapshotError::UnsupportedVersion` if the data was written by an
+    ///   unknown version of the binary format.
+    /// * `SnapshotError::InvalidData` if the data isn't a binary save at all,
+    ///   is truncated, or describes an invalid board.
+    pub fn load_binary(bytes: &[u8]) -> Result<Game, SnapshotError> {
+        let mut reader = ByteReader { bytes };
+        if reader.take(BINARY_MAGIC.len())? != BINARY_MAGIC {
+            return Err(SnapshotError::InvalidData {
+                reason: "not a binary save",
+            });
+        }
+        let version = reader.take(1)?[0];
+        if !(1..=BINARY_VERSION).contains(&version) {
+            return Err(SnapshotError::UnsupportedVersion {
+                version: version.into(),
+            });
+        }
+
+        let axes = reader.u32()?;
+        let dimensions = (0..axes)
+            .map(|_| reader.u64().map(|size| size as usize))
+            .collect::<Result<Vec<usize>, _>>()?;
+        let moves = reader.u64()? as usize;
+        // Version 1 saves stop here, and get the defaults `into_game` gives
+        // every version 1 snapshot.
+        let mut snapshot = GameSnapshot {
+            version: 1,
+            dimensions,
+            mines: Vec::new(),
+            states: Vec::new(),
+            moves,
+            adjacency: Adjacency::default(),
+            wrap: Vec::new(),
+            cascade: true,
+            frozen: Vec::new(),
+            mode: GameMode::default(),
+        };
+        if version >= 2 {
+            snapshot.version = 2;
+            snapshot.adjacency = match reader.take(1)?[0] {
+                0 => Adjacency::Moore,
+                1 => Adjacency::Orthogonal,
+                _ => {
+                    return Err(SnapshotError::InvalidData {
+                        reason: "unknown adjacency rule",
+                    });
+                }
+            };
+            let options = reader.take(1)?[0];
+            if options & !0b11 != 0 {
+                return Err(SnapshotError::InvalidData {
+                    reason: "unknown options",
+                });
+            }
+            snapshot.cascade = options & 0b01 != 0;
+            if options & 0b10 != 0 {
+                snapshot.mode = GameMode::Practice;
+            }
+            let axes = snapshot.dimensions.len();
+            let wrap = reader.take(axes.div_ceil(8))?;
+            snapshot.wrap = (0..axes)
+                .map(|axis| wrap[axis / 8] & (1 << (axis % 8)) != 0)
+                .collect();
+            // Every index takes 8 bytes, so a count the rest of the data
+            // can't hold is corrupt, and must not be allocated for.
+            let frozen = reader.u64()?;
+            if frozen > (reader.bytes.len() / 8) as u64 {
+                return Err(SnapshotError::InvalidData {
+                    reason: "the data is truncated",
+                });
+            }
+            snapshot.frozen = (0..frozen)
+                .map(|_| reader.u64().map(|index| index as usize))
+                .collect::<Result<_, _>>()?;
+        }
+
+        // Check the size against the remaining data *before* allocating
+        // anything, so a corrupted header can't request a giant board.
+        let total_cells = snapshot
+            .dimensions
+            .iter()
+            .try_fold(1_usize, |acc, &d| acc.checked_mul(d))
+            .ok_or(SnapshotError::InvalidData {
+                reason: "the board is too large",
+            })?;
+        let mines_len = total_cells.div_ceil(8);
+        let states_len = total_cells.div_ceil(4);
+        if reader.bytes.len() != mines_len + states_len {
+            return Err(SnapshotError::InvalidData {
+                reason: "the cell data doesn't match the dimensions",
+            });
+        }
+        let mine_bits = reader.take(mines_len)?;
+        let state_bits = reader.take(states_len)?;
+
+        snapshot.mines = (0..total_cells)
+            .filter(|&i| mine_bits[i / 8] & (1 << (i % 8)) != 0)
+            .collect();
+        snapshot.states = (0..total_cells)
+            .map(|i| match (state_bits[i / 4] >> (2 * (i % 4))) & 0b11 {
+                0 => CellState::Hidden,
+                1 => CellState::Revealed,
+                2 => CellState::Flagged,
+                // Two bits can't hold anything else, so every code is valid.
+                _ => CellState::Questioned,
+            })
+            .collect();
+
+        // The rest of the validation is shared with every other encoding.
+        snapshot.into_game()
+    }
+}
+
+/// Reads a binary save front to back, failing cleanly on truncated data.
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    /// Consumes and returns the next `len` bytes.
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.bytes.len() < len {
+            return Err(SnapshotError::InvalidData {
+                reason: "the data is truncated",
+            });
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    /// Consumes a little-endian `u32`.
+    fn u32(&mut self) -> Result<u32, SnapshotError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Consumes a little-endian `u64`.
+    fn u64(&mut self) -> Result<u64, SnapshotError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+impl GameSnapshot {
+    /// Rebuilds the game described by this snapshot.
+    ///
+    /// # Errors
+    ///
+    /// * `SnapshotError::UnsupportedVersion` if the snapshot's version is not
+    ///   one this build can read.
+    /// * `SnapshotError::InvalidData` if the contents are inconsistent, e.g.
+    ///   the number of cell states doesn't match the dimensions.
+    pub fn into_game(self) -> Result<Game, SnapshotError> {
+        match self.version {
+            // Version 1 predates everything after `moves`, whatever the
+            // (defaulted) fields say.
+            1 => Self {
+                adjacency: Adjacency::Moore,
+                wrap: vec![false; self.dimensions.len()],
+                cascade: true,
+                frozen: Vec::new(),
+                mode: GameMode::Standard,
+                ..self
+            }
+            .into_game_v2(),
+            2 => self.into_game_v2(),
+            version => Err(SnapshotError::UnsupportedVersion { version }),
+        }
+    }
+
+    /// Reads a version 2 snapshot.
+    fn into_game_v2(self) -> Result<Game, SnapshotError> {
+        let total_cells = self
+            .dimensions
+            .iter()
+            .try_fold(1_usize, |acc, &d| acc.checked_mul(d))
+            .ok_or(SnapshotError::InvalidData {
+                reason: "the board is too large",
+            })?;
+        if self.states.len() != total_cells {
+            return Err(SnapshotError::InvalidData {
+                reason: "the number of cell states doesn't match the dimensions",
+            });
+        }
+
+        let mut mines = Bitset::new(total_cells);
+        for &index in &self.mines {
+            if index >= total_cells {
+                return Err(SnapshotError::InvalidData {
+                    reason: "a mine lies outside the board",
+                });
+            }
+            mines.set(index, true);
+        }
+
+        if self.wrap.len() != self.dimensions.len() {
+            return Err(SnapshotError::InvalidData {
+                reason: "the number of wrap flags doesn't match the dimensions",
+            });
+        }
+        if self.frozen.iter().any(|&index| index >= total_cells) {
+            return Err(SnapshotError::InvalidData {
+                reason: "a frozen cell lies outside the board",
+            });
+        }
+
+        let mut board = Board::from_mine_bitset(self.dimensions, &mines);
+        board.set_adjacency(self.adjacency);
+        board.set_wrapping_axes(self.wrap);
+        board.set_cascade(self.cascade);
+        for index in self.frozen {
+            let coords = board.coords_of(index);
+            board
+                .set_frozen(&coords, true)
+                .expect("frozen indices were checked above");
+        }
+        board.states = self.states;
+        Ok(Game::resume(board, self.moves, self.mode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+
+    fn played_game() -> Game {
+        let board = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![3, 3]]);
+        let mut game = Game::from_board(board);
+        game.reveal(&vec![1, 1]).unwrap();
+        game.toggle_flag(&vec![0, 0]).unwrap();
+        game
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let game = played_game();
+        let restored = game.snapshot().into_game().unwrap();
+
+        assert!(restored.board().same_layout(game.board()));
+        assert_eq!(restored.snapshot(), game.snapshot());
+        assert_eq!(*restored.state(), GameState::InProgress);
+    }
+
+    fn orthogonal_game() -> Game {
+        let mut board = Board::from_mines(vec![4, 4], &[vec![0, 0], vec![3, 3]]);
+        board.set_adjacency(Adjacency::Orthogonal);
+        let mut game = Game::from_board(board);
+        game.reveal(&vec![1, 1]).unwrap();
+        game
+    }
+
+    #[test]
+    fn test_adjacency_survives_a_round_trip() {
+        let game = orthogonal_game();
+        // Under Moore, (1,1) would touch the mine at (0,0) and stay a '1'.
+        assert_eq!(game.board().revealed_count(), 14);
+
+        let restored = game.snapshot().into_game().unwrap();
+        assert_eq!(restored.board().adjacency(), Adjacency::Orthogonal);
+        assert_eq!(
+            restored.board().adjacency_grid(),
+            game.board().adjacency_grid()
+        );
+        assert_eq!(restored.snapshot(), game.snapshot());
+
+        let restored = Game::load_binary(&game.save_binary()).unwrap();
+        assert_eq!(restored.board().adjacency(), Adjacency::Orthogonal);
+        assert_eq!(restored.snapshot(), game.snapshot());
+    }
+
+    #[test]
+    fn test_version_1_saves_load_as_moore() {
+        let game = orthogonal_game();
+
+        let mut snapshot = game.snapshot();
+        snapshot.version = 1;
+        let restored = snapshot.into_game().unwrap();
+        assert_eq!(restored.board().adjacency(), Adjacency::Moore);
+
+        // A version 1 binary save is the same minus the adjacency and
+        // options bytes, one byte of wrap flags and an empty frozen list,
+        // which follow the magic, version, axis count, 2 axes and moves.
+        let mut bytes = game.save_binary();
+        bytes[4] = 1;
+        let header = 4 + 1 + 4 + 2 * 8 + 8;
+        bytes.drain(header..header + 1 + 1 + 1 + 8);
+        let restored = Game::load_binary(&bytes).unwrap();
+        assert_eq!(restored.board().adjacency(), Adjacency::Moore);
+        assert!(restored.board().same_layout(game.board()));
+        assert!(restored.board().cascade());
+        assert_eq!(restored.board().wrapping_axes(), vec![false, false]);
+    }
+
+    #[test]
+    fn test_board_settings_and_mode_survive_a_round_trip() {
+        let mut board = Board::from_mines(vec![5, 4], &[vec![0, 0], vec![4, 3]]);
+        board.set_wrapping_axes(vec![true, false]);
+        board.set_cascade(false);
+        board.set_frozen(&vec![2, 2], true).unwrap();
+        let mut game = Game::from_board(board);
+        game.set_mode(GameMode::Practice);
+        game.reveal(&vec![0, 0]).unwrap();
+        game.reveal(&vec![2, 1]).unwrap();
+
+        for restored in [
+            game.snapshot().into_game().unwrap(),
+            Game::load_binary(&game.save_binary()).unwrap(),
+        ] {
+            let board = restored.board();
+            assert_eq!(board.wrapping_axes(), vec![true, false]);
+            assert!(!board.cascade());
+            assert!(board.is_frozen(&vec![2, 2]));
+            assert_eq!(board.adjacency_grid(), game.board().adjacency_grid());
+            // The mine hit in practice doesn't turn into a loss.
+            assert_eq!(restored.mode(), GameMode::Practice);
+            assert_eq!(*restored.state(), GameState::InProgress);
+            assert_eq!(restored.mines_hit(), [vec![0, 0]]);
+            assert_eq!(restored.snapshot(), game.snapshot());
+        }
+    }
+
+    #[test]
+    fn test_inconsistent_settings_are_rejected() {
+        let mut snapshot = played_game().snapshot();
+        snapshot.wrap.push(true);
+        assert!(matches!(
+            snapshot.into_game(),
+            Err(SnapshotError::InvalidData { .. })
+        ));
+
+        let mut snapshot = played_game().snapshot();
+        snapshot.frozen.push(16);
+        assert!(matches!(
+            snapshot.into_game(),
+            Err(SnapshotError::InvalidData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_version_is_rejected() {
+        let mut snapshot = played_game().snapshot();
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        assert_eq!(
+            snapshot.into_game().err(),
+            Some(SnapshotError::UnsupportedVersion {
+                version: SNAPSHOT_VERSION + 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_snapshot_is_rejected() {
+        let mut snapshot = played_game().snapshot();
+        snapshot.states.pop();
+        assert!(matches!(
+            snapshot.into_game(),
+            Err(SnapshotError::InvalidData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_binary_round_trip_large_board() {
+        let board = Board::new_seeded(vec![64, 64, 4], 1500, 3).unwrap();
+        let mut game = Game::from_board(board);
+        game.toggle_flag(&vec![0, 0, 0]).unwrap();
+        game.reveal(&vec![32, 32, 2]).unwrap();
+
+        let bytes = game.save_binary();
+        // 16384 cells: 2048 bytes of mines plus 4096 of states, plus header.
+        assert!(bytes.len() < 6200);
+        let restored = Game::load_binary(&bytes).unwrap();
+        assert_eq!(restored.snapshot(), game.snapshot());
+        assert_eq!(restored.state(), game.state());
+    }
+
+    #[test]
+    fn test_binary_rejects_bad_input() {
+        let bytes = played_game().save_binary();
+
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] = b'X';
+        assert!(matches!(
+            Game::load_binary(&wrong_magic),
+            Err(SnapshotError::InvalidData { .. })
+        ));
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = BINARY_VERSION + 1;
+        assert_eq!(
+            Game::load_binary(&wrong_version).err(),
+            Some(SnapshotError::UnsupportedVersion {
+                version: (BINARY_VERSION + 1).into()
+            })
+        );
+
+        assert!(matches!(
+            Game::load_binary(&bytes[..bytes.len() - 1]),
+            Err(SnapshotError::InvalidData { .. })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_binary_is_much_smaller_than_json() {
+        let board = Board::new_seeded(vec![64, 64, 4], 1500, 3).unwrap();
+        let game = Game::from_board(board);
+        let json = serde_json::to_vec(&game.snapshot()).unwrap();
+        assert!(game.save_binary().len() * 5 < json.len());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialized_snapshot_with_unknown_version_fails_cleanly() {
+        let json = serde_json::to_string(&played_game().snapshot()).unwrap();
+        let tampered = json.replace(
+            &format!("\"version\":{SNAPSHOT_VERSION}"),
+            "\"version\":999",
+        );
+        assert_ne!(json, tampered);
+
+        // The data still parses; it's `into_game` that refuses it.
+        let snapshot: GameSnapshot = serde_json::from_str(&tampered).unwrap();
+        assert_eq!(
+            snapshot.into_game().err(),
+            Some(SnapshotError::UnsupportedVersion { version: 999 })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_version_1_json_without_the_newer_fields_loads() {
+        let mut json = serde_json::to_value(orthogonal_game().snapshot()).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.insert("version".to_string(), 1.into());
+        for field in ["adjacency", "wrap", "cascade", "frozen", "mode"] {
+            assert!(fields.remove(field).is_some(), "{field}");
+        }
+
+        let snapshot: GameSnapshot = serde_json::from_value(json).unwrap();
+        let restored = snapshot.into_game().unwrap();
+        assert_eq!(restored.board().adjacency(), Adjacency::Moore);
+        assert!(restored.board().cascade());
+    }
+}
@@ -0,0 +1,665 @@
+// src/solver.rs
+
+//! The `solver` module finds moves that are certain, using only what the
+//! player can see.
+//!
+//! The solver never peeks at hidden cells: it reasons from revealed numbers
+//! and flags alone, exactly like a human player would. That makes it useful
+//! both for hints ("what can I safely do next?") and for checking whether a
+//! board can be cleared without guessing.
+//!
+//! For now it knows the two classic "single point" rules. For a revealed
+//! number `n` with `f` flagged and `h` hidden neighbors:
+//!
+//! * if `f == n`, all its mines are already flagged, so every hidden neighbor
+//!   is safe;
+//! * if `f + h == n`, there is exactly one hidden neighbor per remaining mine,
+//!   so every hidden neighbor is a mine.
+//!
+//! Flags are trusted as placed. A wrong flag leads to wrong deductions, just
+//! as it would mislead a human.
+
+use crate::board::Board;
+use crate::cell::{CellKind, CellState};
+use crate::coordinates::{self, Coordinates, to_coords};
+
+/// What a hint says about its cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HintKind {
+    /// The cell is certainly safe and can be revealed.
+    SafeReveal,
+    /// The cell is certainly a mine and can be flagged.
+    CertainMine,
+}
+
+/// A certain move, together with the reasoning that makes it certain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hint {
+    /// The cell the hint is about.
+    pub coords: Coordinates,
+    /// Whether the cell is safe or a mine.
+    pub kind: HintKind,
+    /// A human-readable explanation of the deduction, meant to teach the
+    /// player how to find such moves themselves.
+    pub because: String,
+}
+
+/// What the solver has worked out about a hidden cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Knowledge {
+    /// Nothing yet.
+    Unknown,
+    /// Certainly safe.
+    Safe,
+    /// Certainly a mine (or flagged, which the solver trusts).
+    Mine,
+}
+
+/// Finds certain moves on a board.
+///
+/// The solver only borrows the board; it never changes it.
+pub struct Solver<'a> {
+    board: &'a Board,
+}
+
+impl<'a> Solver<'a> {
+    /// Creates a solver for `board`.
+    pub fn new(board: &'a Board) -> Self {
+        Self { board }
+    }
+
+    /// Returns the first certain move the single point rules can find, or
+    /// `None` if there is none (the player would have to guess, or use
+    /// deeper reasoning than this solver knows).
+    ///
+    /// Numbers are examined in flat index order, so the result is
+    /// deterministic.
+    pub fn next_hint(&self) -> Option<Hint> {
+        let board = self.board;
+        (0..board.cell_count())
+            .filter(|&index| board.states[index] == CellState::Revealed)
+            .find_map(|index| match board.kind_at(index) {
+                CellKind::Empty { adjacent_mines } => {
+                    self.hint_from_number(&self.board.coords_of(index), adjacent_mines as usize)
+                }
+                CellKind::Mine => None,
+            })
+    }
+
+    /// Returns the hidden cells next to revealed numbers whose fate logic
+    /// can't decide.
+    ///
+    /// The single point rules are applied over and over, each round building
+    /// on what the previous ones deduced, until nothing new turns up. The
+    /// cells left over on the frontier (the hidden cells touching a revealed
+    /// number) are exactly where the player will have to guess, e.g. both
+    /// cells of a classic 50/50. An empty result means logic alone can make
+    /// progress, or that there is no frontier at all. Frozen cells are left
+    /// out, since the player can't guess on them either.
+    ///
+    /// The cells are returned in flat index order.
+    pub fn frontier(&self) -> Vec<Coordinates> {
+        let knowledge = self.deduce();
+        (0..self.board.cell_count())
+            .filter(|&i| self.board.states[i].is_hidden() && knowledge[i] == Knowledge::Unknown)
+            .filter(|&i| !self.board.is_frozen(&self.board.coords_of(i)))
+            .filter(|&i| {
+                self.board
+                    .neighbor_indices(i)
+                    .into_iter()
+                    .any(|n| self.board.states[n] == CellState::Revealed)
+            })
+            .map(|i| self.board.coords_of(i))
+            .collect()
+    }
+
+    /// Estimates, for every cell (by flat index), the chance that it is a
+    /// mine, or `None` for cells the player can already see or has flagged.
+    ///
+    /// Cells the single point rules decide get `0.0` or `1.0`. The mines
+    /// left unaccounted for are then assumed to be spread evenly over the
+    /// remaining undecided cells. Exact probabilities would mean counting
+    /// every mine layout consistent with the numbers, which takes
+    /// exponential time; this estimate is cheap and never leaves `0.0..=1.0`.
+    fn probabilities(&self) -> Vec<Option<f64>> {
+        let board = self.board;
+        let knowledge = self.deduce();
+        let known_mines = knowledge.iter().filter(|&&k| k == Knowledge::Mine).count();
+        let undecided = (0..board.cell_count())
+            .filter(|&i| board.states[i].is_hidden() && knowledge[i] == Knowledge::Unknown)
+            .count();
+        // Wrong flags can make the known mines outnumber the real ones.
+        let remaining = board.num_mines().saturating_sub(known_mines);
+        let density = if undecided == 0 {
+            0.0
+        } else {
+            (remaining as f64 / undecided as f64).min(1.0)
+        };
+
+        board
+            .states
+            .iter()
+            .zip(knowledge)
+            .map(|(state, known)| {
+                if !state.is_hidden() {
+                    return None;
+                }
+                Some(match known {
+                    Knowledge::Safe => 0.0,
+                    Knowledge::Mine => 1.0,
+                    Knowledge::Unknown => density,
+                })
+            })
+            .collect()
+    }
+
+    /// Applies the single point rules until they stop finding anything, and
+    /// returns what is known about every cell (by flat index).
+    ///
+    /// Unlike `next_hint`, this chains deductions: a mine deduced from one
+    /// number counts towards the neighbors' numbers in the next round, just
+    /// as if the player had flagged it.
+    fn deduce(&self) -> Vec<Knowledge> {
+        let board = self.board;
+        let mut knowledge: Vec<Knowledge> = (0..board.cell_count())
+            .map(
+                |index| match (&board.states[index], board.mines.get(index)) {
+                    // A mine revealed in practice mode is as good as a flag.
+                    (CellState::Flagged, _) | (CellState::Revealed, true) => Knowledge::Mine,
+                    _ => Knowledge::Unknown,
+                },
+            )
+            .collect();
+
+        let numbers: Vec<(usize, usize)> = (0..board.cell_count())
+            .filter(|&index| board.states[index] == CellState::Revealed)
+            .filter_map(|index| match board.kind_at(index) {
+                CellKind::Empty { adjacent_mines } => Some((index, adjacent_mines as usize)),
+                CellKind::Mine => None,
+            })
+            .collect();
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &(index, number) in &numbers {
+                let neighbors = board.neighbor_indices(index);
+                let known_mines = neighbors
+                    .iter()
+                    .filter(|&&n| knowledge[n] == Knowledge::Mine)
+                    .count();
+                let unknown: Vec<usize> = neighbors
+                    .into_iter()
+                    .filter(|&n| board.states[n].is_hidden() && knowledge[n] == Knowledge::Unknown)
+                    .collect();
+                if unknown.is_empty() {
+                    continue;
+                }
+
+                let verdict = if known_mines == number {
+                    Knowledge::Safe
+                } else if known_mines + unknown.len() == number {
+                    Knowledge::Mine
+                } else {
+                    continue;
+                };
+                for n in unknown {
+                    knowledge[n] = verdict;
+                }
+                changed = true;
+            }
+        }
+        knowledge
+    }
+
+    /// Applies the single point rules to the revealed number at `coords`.
+    ///
+    /// Frozen cells still count as hidden for the deduction (they may well
+    /// be mines), but are never the target: the player can't act on them.
+    /// Mines revealed in practice mode count as flags.
+    fn hint_from_number(&self, coords: &Coordinates, number: usize) -> Option<Hint> {
+        let hidden = self.board.hidden_neighbors(coords);
+        let flagged = self
+            .board
+            .neighbor_indices(self.board.index_of(coords))
+            .into_iter()
+            .filter(|&n| match self.board.states[n] {
+                CellState::Flagged => true,
+                CellState::Revealed => self.board.mines.get(n),
+                CellState::Hidden | CellState::Questioned => false,
+            })
+            .count();
+        // Nothing left to deduce around this number that can be acted on.
+        let target = hidden
+            .iter()
+            .find(|cell| !self.board.is_frozen(cell))?
+            .clone();
+        let at = coordinates::format(coords);
+
+        if flagged == number {
+            Some(Hint {
+                coords: target,
+                kind: HintKind::SafeReveal,
+                because: format!(
+                    "the '{number}' at ({at}) already touches {flagged} {}, \
+                     so its other hidden neighbors are safe",
+                    plural(flagged, "flag", "flags")
+                ),
+            })
+        } else if flagged + hidden.len() == number {
+            let remaining = number - flagged;
+            Some(Hint {
+                coords: target,
+                kind: HintKind::CertainMine,
+                because: format!(
+                    "the '{number}' at ({at}) still needs {remaining} {} and has \
+                     exactly {remaining} hidden {}, so they are all mines",
+                    plural(remaining, "mine", "mines"),
+                    plural(remaining, "neighbor", "neighbors")
+                ),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Counts how many times a player would have to guess to clear `board`.
+///
+/// This plays the board from scratch on a copy (the original is untouched):
+/// every certain move the solver finds is made, and whenever it is stuck a
+/// guess is counted. The simulation is optimistic about guesses: it peeks at
+/// the answer key and picks the safe cell with the fewest adjacent mines,
+/// i.e. the most helpful one. The very first click is always a guess.
+pub fn guesses_needed(board: &Board) -> usize {
+    let dimensions = board.dimensions().to_vec();
+    let mut play = Board::from_mine_bitset(dimensions.clone(), &board.mine_bitset());
+    play.set_adjacency(board.adjacency());
+    play.set_wrapping_axes(board.wrapping_axes());
+    let total_safe = play.safe_cells();
+    let mut guesses = 0;
+
+    while revealed_safe_count(&play) < total_safe {
+        let progressed = play
+            .explained_hint()
+            .is_some_and(|hint| apply_hint(&mut play, &hint));
+        if !progressed {
+            guesses += 1;
+            let best = (0..play.cell_count())
+                .filter(|&index| play.states[index].is_hidden())
+                .filter_map(|index| match play.kind_at(index) {
+                    CellKind::Empty { adjacent_mines } => Some((adjacent_mines, index)),
+                    CellKind::Mine => None,
+                })
+                .min()
+                .map(|(_, index)| index)
+                .expect("an unrevealed safe cell exists while the loop runs");
+            play.reveal(&to_coords(best, &dimensions));
+        }
+    }
+    guesses
+}
+
+/// Returns how many safe cells of `board` are revealed, leaving out any
+/// mines revealed in practice mode.
+fn revealed_safe_count(board: &Board) -> usize {
+    (0..board.cell_count())
+        .filter(|&index| board.states[index] == CellState::Revealed && !board.mines.get(index))
+        .count()
+}
+
+/// Makes the move `hint` suggests on `play`, and returns whether the cell
+/// changed.
+///
+/// A hint the board refuses to apply would otherwise be suggested again and
+/// again, so callers treat `false` as being stuck.
+fn apply_hint(play: &mut Board, hint: &Hint) -> bool {
+    let before = play.cell_state(&hint.coords);
+    match hint.kind {
+        HintKind::SafeReveal => {
+            play.reveal(&hint.coords);
+        }
+        HintKind::CertainMine => {
+            play.toggle_flag(&hint.coords);
+        }
+    }
+    play.cell_state(&hint.coords) != before
+}
+
+/// Picks the singular or plural form of a word for `count`.
+fn plural(count: usize, singular: &'static str, plural: &'static str) -> &'static str {
+    if count == 1 { singular } else { plural }
+}
+
+impl Board {
+    /// Returns a certain move along with an explanation of why it is certain,
+    /// or `None` if the visible information doesn't force any move.
+    ///
+    /// See the `solver` module for the rules used.
+    pub fn explained_hint(&self) -> Option<Hint> {
+        Solver::new(self).next_hint()
+    }
+
+    /// Returns `true` if the player can't clear the board from here without
+    /// guessing, e.g. because it comes down to a 50/50.
+    ///
+    /// This plays on from the current state on a copy of the board, making
+    /// every certain move the solver finds (see the `solver` module), and
+    /// reports whether it gets stuck before every safe cell is revealed. A
+    /// UI can use it to warn the player, or to offer a free reveal. A board
+    /// nobody has clicked yet always needs a guess: the first click.
+    ///
+    /// Like the solver, this trusts the player's flags. A wrong flag can
+    /// make the answer wrong, too.
+    pub fn has_forced_guess(&self) -> bool {
+        let mut play = self.clone();
+        play.set_reveal_limit(None);
+        let total_safe = play.safe_cells();
+
+        // Mines revealed in practice mode don't bring the player any closer
+        // to clearing the board.
+        while revealed_safe_count(&play) < total_safe {
+            let Some(hint) = play.explained_hint() else {
+                return true;
+            };
+            if !apply_hint(&mut play, &hint) {
+                return true;
+            }
+            // Only a wrong flag can lead the solver onto a mine; whatever
+            // comes next isn't about guessing any more.
+            if hint.kind == HintKind::SafeReveal && play.is_mine(&hint.coords) == Some(true) {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// Returns every hidden cell together with the estimated chance that it
+    /// is a mine, in flat index order. Flagged and revealed cells are left
+    /// out.
+    ///
+    /// See `probabilities_grid` for how the chances are estimated.
+    pub fn mine_probabilities(&self) -> Vec<(Coordinates, f64)> {
+        self.probabilities_grid()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, p)| Some((self.coords_of(index), p?)))
+            .collect()
+    }
+
+    /// Returns the estimated chance that each cell is a mine, in flat index
+    /// order, with `None` for revealed and flagged cells. This is handy for
+    /// drawing a risk heatmap.
+    ///
+    /// Only what the player can see is used: cells the single point rules
+    /// (see the `solver` module) decide get `0.0` or `1.0`, and the mines not
+    /// yet accounted for are spread evenly over the remaining hidden cells.
+    /// The result is an estimate, not an exact probability.
+    pub fn probabilities_grid(&self) -> Vec<Option<f64>> {
+        Solver::new(self).probabilities()
+    }
+
+    /// Estimates how hard the board is, as a score from `0.0` (trivial) to
+    /// `100.0` (as hard as it gets).
+    ///
+    /// The score adds up three terms, each scaled to `0.0..=1.0` first:
+    ///
+    /// * **Mine density** (weight 40): mines per cell. Denser boards have more
+    ///   numbers to read and fewer openings.
+    /// * **3BV per safe cell** (weight 20): how many clicks the board needs
+    ///   relative to its size (see `compute_3bv`). Boards that open up in a
+    ///   few big cascades score low.
+    /// * **Guessing** (weight 40): the fraction of safe cells the player must
+    ///   guess (see `solver::guesses_needed`), not counting the first click,
+    ///   which is a guess on every board. Guessing weighs as much as density
+    ///   because no amount of skill helps with it.
+    ///
+    /// The weights are a judgment call, not a standard; the score is meant
+    /// for comparing boards with each other, e.g. to sort generated puzzles.
+    pub fn estimate_difficulty(&self) -> f64 {
+        let total_cells = self.cell_count();
+        let total_safe = self.safe_cells();
+        if total_safe == 0 {
+            // Nothing to uncover; only the density says anything.
+            return if total_cells == 0 { 0.0 } else { 40.0 };
+        }
+
+        let density = self.num_mines() as f64 / total_cells as f64;
+        let clicks = self.compute_3bv() as f64 / total_safe as f64;
+        let forced_guesses = guesses_needed(self).saturating_sub(1);
+        let guessing = forced_guesses as f64 / total_safe as f64;
+        40.0 * density + 20.0 * clicks + 40.0 * guessing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Adjacency;
+
+    #[test]
+    fn test_explained_hint_certain_mine() {
+        // Mine at (2,0). The '1' at (2,1) has (2,0) as its only hidden
+        // neighbor once (1,0) and (1,1) are revealed too.
+        let mut board = Board::from_mines(vec![3, 2], &[vec![2, 0]]);
+        board.reveal(&vec![1, 0]);
+        board.reveal(&vec![1, 1]);
+        board.reveal(&vec![2, 1]);
+
+        let hint = board.explained_hint().unwrap();
+        assert_eq!(hint.coords, vec![2, 0]);
+        assert_eq!(hint.kind, HintKind::CertainMine);
+        assert!(!hint.because.is_empty());
+    }
+
+    #[test]
+    fn test_explained_hint_safe_reveal_after_flag() {
+        // Mine at (0,0); (1,0) and (1,1) are '1's. Once (0,0) is flagged,
+        // the remaining hidden neighbor (0,1) is safe.
+        let mut board = Board::from_mines(vec![2, 2], &[vec![0, 0]]);
+        board.reveal(&vec![1, 0]);
+        board.reveal(&vec![1, 1]);
+        board.toggle_flag(&vec![0, 0]);
+
+        let hint = board.explained_hint().unwrap();
+        assert_eq!(hint.coords, vec![0, 1]);
+        assert_eq!(hint.kind, HintKind::SafeReveal);
+        assert!(hint.because.contains("(1,0)"));
+    }
+
+    #[test]
+    fn test_has_forced_guess() {
+        // Two '1's share the same two hidden neighbors: a classic 50/50.
+        let mut board = Board::from_mines(vec![2, 2], &[vec![0, 0]]);
+        board.reveal(&vec![1, 0]);
+        board.reveal(&vec![1, 1]);
+        assert!(board.has_forced_guess());
+
+        // Here the mine at (2,0) is certain, and once it is flagged the
+        // '1' at (1,0) says the rest of its neighbors are safe.
+        let mut board = Board::from_mines(vec![3, 2], &[vec![2, 0]]);
+        board.reveal(&vec![1, 0]);
+        board.reveal(&vec![1, 1]);
+        board.reveal(&vec![2, 1]);
+        assert!(!board.has_forced_guess());
+        // The check ran on a copy.
+        assert_eq!(board.revealed_count(), 3);
+        assert_eq!(board.flag_count(), 0);
+
+        // Before the first click, there is nothing to deduce from.
+        assert!(Board::from_mines(vec![3, 2], &[vec![2, 0]]).has_forced_guess());
+    }
+
+    #[test]
+    fn test_frozen_cells_are_never_hinted() {
+        // Row "* 1 F 0": the '0' at (3,0) says (2,0) is safe, but (2,0) is
+        // frozen, so neither reveal nor flag can act on it.
+        let mut board = Board::from_mines(vec![4, 1], &[vec![0, 0]]);
+        board.set_frozen(&vec![2, 0], true).unwrap();
+        board.reveal(&vec![3, 0]);
+        assert_eq!(board.revealed_count(), 1);
+
+        assert_eq!(board.explained_hint(), None);
+        assert!(Solver::new(&board).frontier().is_empty());
+        // Without a usable hint, this has to end rather than loop forever.
+        assert!(board.has_forced_guess());
+    }
+
+    #[test]
+    fn test_has_forced_guess_ignores_revealed_mines() {
+        // Row "* 1 1 *" after a practice game stepped on both mines: three
+        // cells are revealed, but only one of the two safe cells is. The
+        // revealed mines pin down the rest, so no guess is needed.
+        let mut board = Board::from_mines(vec![4, 1], &[vec![0, 0], vec![3, 0]]);
+        board.reveal(&vec![0, 0]);
+        board.reveal(&vec![1, 0]);
+        board.reveal(&vec![3, 0]);
+        assert_eq!(board.revealed_count(), 3);
+        assert_eq!(board.explained_hint().unwrap().coords, vec![2, 0]);
+        assert!(!board.has_forced_guess());
+
+        // Without the revealed mine at (0,0), the '1' at (1,0) is a 50/50.
+        let mut board = Board::from_mines(vec![4, 1], &[vec![0, 0], vec![3, 0]]);
+        board.reveal(&vec![1, 0]);
+        assert!(board.has_forced_guess());
+
+        // Two revealed mines and two revealed '3's make four revealed cells,
+        // more than the three safe ones, yet (0,0) vs (0,1) is still a 50/50.
+        let mut board = Board::from_mines(vec![3, 2], &[vec![0, 0], vec![2, 0], vec![2, 1]]);
+        for coords in [vec![1, 0], vec![1, 1], vec![2, 0], vec![2, 1]] {
+            board.reveal(&coords);
+        }
+        assert_eq!(board.revealed_count(), 4);
+        assert!(board.has_forced_guess());
+    }
+
+    #[test]
+    fn test_guesses_needed() {
+        // Everything cascades from one click.
+        assert_eq!(guesses_needed(&Board::from_mines(vec![4, 4], &[])), 1);
+        // Row "* 1 1 *": the first '1' revealed sees two hidden cells and
+        // can't tell which one is the mine, so the other '1' is a guess too.
+        let board = Board::from_mines(vec![4, 1], &[vec![0, 0], vec![3, 0]]);
+        assert_eq!(guesses_needed(&board), 2);
+    }
+
+    #[test]
+    fn test_estimate_difficulty_empty_vs_dense() {
+        let empty = Board::from_mines(vec![10, 10], &[]);
+        assert!(empty.estimate_difficulty() < 1.0);
+
+        let dense = Board::new_seeded(vec![8, 8], 24, 5).unwrap();
+        assert!(dense.estimate_difficulty() > 15.0);
+    }
+
+    #[test]
+    fn test_frontier_of_a_fifty_fifty() {
+        // Two '1's side by side, sharing the same two hidden neighbors: the
+        // mine is in one of them, and nothing tells which.
+        let mut board = Board::from_mines(vec![2, 2], &[vec![0, 1]]);
+        board.reveal(&vec![0, 0]);
+        board.reveal(&vec![1, 0]);
+
+        let frontier = Solver::new(&board).frontier();
+        assert_eq!(frontier, vec![vec![0, 1], vec![1, 1]]);
+    }
+
+    #[test]
+    fn test_frontier_excludes_deducible_cells() {
+        // The '1' at (2,1) pins the mine on (2,0). With that mine known, the
+        // '1' at (1,0) clears (0,0) and (0,1), so nothing is left to guess.
+        let mut board = Board::from_mines(vec![3, 2], &[vec![2, 0]]);
+        board.reveal(&vec![1, 0]);
+        board.reveal(&vec![1, 1]);
+        board.reveal(&vec![2, 1]);
+        assert!(Solver::new(&board).frontier().is_empty());
+    }
+
+    #[test]
+    fn test_hints_follow_orthogonal_adjacency() {
+        // Mine at (1,1). Seen orthogonally, (0,0) is a zero and opens (1,0)
+        // and (0,1), both '1's. (0,1) only touches (0,0) and (1,1), so (1,1)
+        // must be the mine; with Moore adjacency it would touch (1,0) too.
+        let mut board = Board::from_mines(vec![3, 2], &[vec![1, 1]]);
+        board.set_adjacency(Adjacency::Orthogonal);
+        board.reveal(&vec![0, 0]);
+
+        let hint = board.explained_hint().unwrap();
+        assert_eq!(hint.coords, vec![1, 1]);
+        assert_eq!(hint.kind, HintKind::CertainMine);
+    }
+
+    #[test]
+    fn test_probabilities_grid() {
+        // The '1' at (2,1) pins the mine on (2,0), which in turn clears
+        // (0,0) and (0,1).
+        let mut board = Board::from_mines(vec![3, 2], &[vec![2, 0]]);
+        board.reveal(&vec![1, 0]);
+        board.reveal(&vec![1, 1]);
+        board.reveal(&vec![2, 1]);
+
+        let grid = board.probabilities_grid();
+        assert_eq!(grid.len(), 6);
+        for (index, p) in grid.iter().enumerate() {
+            if board.cell_at(index).state == CellState::Revealed {
+                assert_eq!(*p, None);
+            } else {
+                assert!((0.0..=1.0).contains(&p.unwrap()));
+            }
+        }
+        assert_eq!(grid[0], Some(0.0));
+        assert_eq!(grid[2], Some(1.0));
+        assert_eq!(
+            board.mine_probabilities(),
+            vec![(vec![0, 0], 0.0), (vec![2, 0], 1.0), (vec![0, 1], 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_probabilities_spread_undecided_mines_evenly() {
+        // With nothing revealed, every cell is equally likely.
+        let mines = [vec![0, 0], vec![1, 2], vec![3, 3], vec![2, 1]];
+        let mut board = Board::from_mines(vec![4, 4], &mines);
+        assert!(board.probabilities_grid().iter().all(|&p| p == Some(0.25)));
+
+        // A flag accounts for one mine: 3 left among 15 cells.
+        board.toggle_flag(&vec![0, 0]);
+        let grid = board.probabilities_grid();
+        assert_eq!(grid[0], None);
+        assert!(grid[1..].iter().all(|&p| p == Some(0.2)));
+    }
+
+    #[test]
+    fn test_index_keyed_probabilities_match_coordinate_keyed() {
+        use crate::coordinates::{by_coords, by_index};
+        use std::collections::HashMap;
+
+        let mines = [vec![0, 0], vec![3, 1], vec![1, 3]];
+        let mut board = Board::from_mines(vec![4, 4], &mines);
+        board.reveal(&vec![1, 1]);
+        board.reveal(&vec![2, 2]);
+        board.toggle_flag(&vec![0, 0]);
+        let dimensions = board.dimensions();
+
+        let by_coordinates: HashMap<Coordinates, f64> =
+            board.mine_probabilities().into_iter().collect();
+        let by_flat_index: HashMap<usize, f64> = board
+            .probabilities_grid()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, p)| Some((index, p?)))
+            .collect();
+
+        assert!(!by_flat_index.is_empty());
+        assert_eq!(by_index(by_coordinates.clone(), dimensions), by_flat_index);
+        assert_eq!(by_coords(by_flat_index, dimensions), by_coordinates);
+    }
+
+    #[test]
+    fn test_explained_hint_none_when_guess_needed() {
+        // A lone '1' with two hidden neighbors could go either way.
+        let mut board = Board::from_mines(vec![3, 1], &[vec![0, 0]]);
+        board.reveal(&vec![1, 0]);
+        assert_eq!(board.explained_hint(), None);
+    }
+}